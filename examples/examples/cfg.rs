@@ -0,0 +1,80 @@
+use serdev::Deserialize;
+
+/* `#[cfg]` and `#[cfg_attr]` are resolved before deriving, so they work on validated types
+   in the same way as on plain ones. `not(any())` is always true and `any()` is always false,
+   standing for the predicates like `feature = "..."` */
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(validate = "Self::validate")]
+#[cfg_attr(not(any()), serde(deny_unknown_fields))]
+struct Config {
+    #[serdev(range(min = 1))]
+    workers: u8,
+    #[cfg(not(any()))]
+    #[cfg_attr(not(any()), serde(rename = "log"))]
+    #[serdev(length(min = 1))]
+    log_level: String,
+    #[cfg(any())]
+    #[serdev(length(min = 1))]
+    telemetry: String,
+    #[cfg_attr(any(), serde(rename = "never"))]
+    name: String,
+}
+impl Config {
+    fn validate(&self) -> Result<(), impl std::fmt::Display> {
+        if self.workers > 64 {
+            return Err("too many workers")
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+enum Storage {
+    Memory,
+    #[cfg(any())]
+    Remote {
+        #[serdev(format = "url")]
+        url: String,
+    },
+    #[cfg_attr(not(any()), serde(rename = "disk"))]
+    Disk {
+        #[serdev(length(min = 1))]
+        path: String,
+        #[cfg(not(any()))]
+        #[serdev(range(max = 100))]
+        quota: u8,
+    },
+}
+
+fn main() {
+    assert_eq!(
+        serde_json::from_str::<Config>(r#"{"workers":4,"log":"info","name":"app"}"#).unwrap(),
+        Config { workers: 4, log_level: String::from("info"), name: String::from("app") }
+    );
+    assert_eq!(
+        serde_json::from_str::<Config>(r#"{"workers":4,"log":"","name":"app"}"#).unwrap_err().to_string(),
+        "log: length must be greater than or equal to 1"
+    );
+    assert_eq!(
+        serde_json::from_str::<Config>(r#"{"workers":100,"log":"info","name":"app"}"#).unwrap_err().to_string(),
+        "too many workers"
+    );
+    assert!(
+        serde_json::from_str::<Config>(r#"{"workers":4,"log":"info","name":"app","telemetry":"on"}"#).unwrap_err().to_string()
+            .starts_with("unknown field `telemetry`")
+    );
+
+    assert_eq!(
+        serde_json::from_str::<Storage>(r#"{"disk":{"path":"/tmp","quota":50}}"#).unwrap(),
+        Storage::Disk { path: String::from("/tmp"), quota: 50 }
+    );
+    assert_eq!(
+        serde_json::from_str::<Storage>(r#"{"disk":{"path":"/tmp","quota":200}}"#).unwrap_err().to_string(),
+        "disk.quota: must be less than or equal to 100"
+    );
+    assert!(
+        serde_json::from_str::<Storage>(r#"{"Remote":{"url":"https://ohkami.rs"}}"#).unwrap_err().to_string()
+            .starts_with("unknown variant `Remote`")
+    );
+}
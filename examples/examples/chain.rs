@@ -0,0 +1,82 @@
+use serdev::Deserialize;
+
+
+mod rules {
+    pub fn not_blank(s: &str) -> Result<(), &'static str> {
+        if s.trim().is_empty() {return Err("must not be blank")}
+        Ok(())
+    }
+    pub fn ascii(s: &str) -> Result<(), &'static str> {
+        if !s.is_ascii() {return Err("must be ASCII")}
+        Ok(())
+    }
+
+    #[derive(Debug)]
+    pub struct PolicyError(pub String);
+    impl std::fmt::Display for PolicyError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+    impl From<serdev::Error> for PolicyError {
+        fn from(e: serdev::Error) -> Self {
+            Self(e.to_string())
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(validate = "Self::size", validate = "Self::owner")]
+struct Team {
+    #[serde(validate = [rules::not_blank, rules::ascii])]
+    name:    String,
+    members: Vec<String>,
+    owner:   String,
+}
+impl Team {
+    fn size(&self) -> Result<(), impl std::fmt::Display> {
+        if self.members.len() > 5 {return Err("too many members")}
+        Ok(())
+    }
+    fn owner(&self) -> Result<(), impl std::fmt::Display> {
+        if !self.members.contains(&self.owner) {return Err("owner must be a member")}
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(validate = "Self::range")]
+#[serde(validate(by = "Self::policy", error = "rules::PolicyError"))]
+struct Quota {
+    used:  u32,
+    limit: u32,
+}
+impl Quota {
+    fn range(&self) -> Result<(), impl std::fmt::Display> {
+        if self.used > self.limit {return Err("used must not exceed limit")}
+        Ok(())
+    }
+    fn policy(&self) -> Result<(), rules::PolicyError> {
+        if self.limit > 100 {return Err(rules::PolicyError(format!("limit {} is over the policy", self.limit)))}
+        Ok(())
+    }
+}
+
+fn main() {
+    let team = |json: &str| serde_json::from_str::<Team>(json).map_err(|e| e.to_string());
+
+    assert!(team(r#"{"name":"core","members":["a","b"],"owner":"a"}"#).is_ok());
+    assert_eq!(team(r#"{"name":" ","members":["a"],"owner":"a"}"#).unwrap_err(), "name: must not be blank");
+    assert_eq!(team(r#"{"name":"コア","members":["a"],"owner":"a"}"#).unwrap_err(), "name: must be ASCII");
+    assert_eq!(
+        team(r#"{"name":"core","members":["a","b","c","d","e","f"],"owner":"x"}"#).unwrap_err(),
+        "too many members"
+    );
+    assert_eq!(team(r#"{"name":"core","members":["a"],"owner":"x"}"#).unwrap_err(), "owner must be a member");
+
+    let quota = |json: &str| serde_json::from_str::<Quota>(json).map_err(|e| e.to_string());
+
+    assert!(quota(r#"{"used":1,"limit":10}"#).is_ok());
+    assert_eq!(quota(r#"{"used":11,"limit":10}"#).unwrap_err(), "used must not exceed limit");
+    assert_eq!(quota(r#"{"used":1,"limit":1000}"#).unwrap_err(), "limit 1000 is over the policy");
+}
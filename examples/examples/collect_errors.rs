@@ -0,0 +1,111 @@
+use serdev::Deserialize;
+
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serdev(collect_errors)]
+#[serde(validate = "Self::validate")]
+struct Signup {
+    #[serdev(length(min = 1))]
+    name: String,
+    #[serdev(format = "email")]
+    mail: String,
+    #[serdev(range(min = 18))]
+    age: u8,
+    password: String,
+    password_confirmation: String,
+}
+impl Signup {
+    fn validate(&self) -> Result<(), impl std::fmt::Display> {
+        if self.password != self.password_confirmation {
+            return Err("passwords must match")
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct Problems(Vec<String>);
+impl Extend<serdev::Error> for Problems {
+    fn extend<I: IntoIterator<Item = serdev::Error>>(&mut self, iter: I) {
+        self.0.extend(iter.into_iter().map(|e| format!("[{}] {e}", e.code())))
+    }
+}
+impl Extend<(&'static str, u8)> for Problems {
+    fn extend<I: IntoIterator<Item = (&'static str, u8)>>(&mut self, iter: I) {
+        self.0.extend(iter.into_iter().map(|(what, n)| format!("{what} ({n})")))
+    }
+}
+impl std::fmt::Display for Problems {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} problem(s): {}", self.0.len(), self.0.join(" / "))
+    }
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serdev(collect_errors)]
+#[serde(validate(by = "Self::validate", error = "Problems"))]
+struct Pair {
+    #[serdev(range(max = 10))]
+    a: u8,
+    #[serdev(range(max = 10))]
+    b: u8,
+}
+impl Pair {
+    fn validate(&self) -> Result<(), (&'static str, u8)> {
+        if self.a + self.b > 15 {
+            return Err(("sum is too large", self.a + self.b))
+        }
+        Ok(())
+    }
+}
+
+fn main() {
+    assert_eq!(
+        serde_json::from_str::<Signup>(r#"{
+            "name": "serdev",
+            "mail": "serdev@ohkami.rs",
+            "age": 20,
+            "password": "wolf",
+            "password_confirmation": "wolf"
+        }"#).unwrap(),
+        Signup {
+            name:                  String::from("serdev"),
+            mail:                  String::from("serdev@ohkami.rs"),
+            age:                   20,
+            password:              String::from("wolf"),
+            password_confirmation: String::from("wolf")
+        }
+    );
+    assert_eq!(
+        serde_json::from_str::<Signup>(r#"{
+            "name": "",
+            "mail": "serdev",
+            "age": 2,
+            "password": "wolf",
+            "password_confirmation": "dog"
+        }"#).unwrap_err().to_string(),
+        "name: length must be greater than or equal to 1; \
+        mail: must be a valid email address; \
+        age: must be greater than or equal to 18; \
+        passwords must match"
+    );
+    assert_eq!(
+        serde_json::from_str::<Signup>(r#"{
+            "name": "serdev",
+            "mail": "serdev",
+            "age": 20,
+            "password": "wolf",
+            "password_confirmation": "wolf"
+        }"#).unwrap_err().to_string(),
+        "mail: must be a valid email address"
+    );
+
+    assert_eq!(
+        serde_json::from_str::<Pair>(r#"{"a":1,"b":2}"#).unwrap(),
+        Pair { a: 1, b: 2 }
+    );
+    assert_eq!(
+        serde_json::from_str::<Pair>(r#"{"a":11,"b":12}"#).unwrap_err().to_string(),
+        "3 problem(s): [range] a: must be less than or equal to 10 / [range] b: must be less than or equal to 10 / sum is too large (23)"
+    );
+}
@@ -0,0 +1,136 @@
+use serdev::{Deserialize, PathSegment};
+
+
+#[derive(Debug, Deserialize)]
+#[serde(validate = "Self::validate")]
+struct Item {
+    #[serdev(length(min = 1, max = 16))]
+    sku: String,
+    #[serdev(range(min = 1))]
+    quantity: u32,
+}
+impl Item {
+    fn validate(&self) -> Result<(), impl std::fmt::Display> {
+        if self.sku.starts_with("DISCONTINUED-") && self.quantity > 0 {
+            return Err("discontinued item can't be ordered")
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(validate(by = "Self::validate", error = "serdev::Error"))]
+struct Coupon {
+    code: String,
+}
+impl Coupon {
+    fn validate(&self) -> Result<(), serdev::Error> {
+        if !self.code.starts_with("SV") {
+            return Err(serdev::Error::new("coupon_prefix", "coupon code must start with `SV`")
+                .with_param("prefix", "SV"))
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(unused)]
+#[serde(validate(by = "Self::validate", error = "String"))]
+struct Parcel {
+    #[serdev(range(min = 1))]
+    weight: u32,
+}
+impl Parcel {
+    fn validate(&self) -> Result<(), String> {
+        if self.weight > 30 {
+            return Err(format!("{}kg is too heavy", self.weight))
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(unused)]
+#[serdev(collect_errors)]
+struct Address {
+    #[serdev(length(equal = 7), pattern = "^[0-9]+$")]
+    zip: String,
+    #[serdev(length(min = 1))]
+    city: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(validate = "Self::validate")]
+struct Token(String);
+impl Token {
+    fn validate(&self) -> Result<(), impl std::fmt::Display> {
+        if !self.0.starts_with("sv_") {
+            return Err("invalid")
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(unused)]
+struct Label<'l> {
+    #[serdev(length(min = 1))]
+    text: &'l str,
+}
+
+fn main() {
+    let e = serde_json::from_str::<Item>(r#"{"sku":"","quantity":1}"#).unwrap_err();
+    assert_eq!(e.to_string(), "sku: length must be greater than or equal to 1");
+    let e = serdev::Error::recover(&e).unwrap();
+    assert_eq!(e.code(), "length");
+    assert_eq!(e.message(), "length must be greater than or equal to 1");
+    assert_eq!(e.path(), [PathSegment::Field("sku".into())]);
+    assert_eq!(e.param("min"), Some("1"));
+
+    let e = serde_json::from_str::<Item>(r#"{"sku":"DISCONTINUED-1","quantity":1}"#).unwrap_err();
+    let e = serdev::Error::recover(&e).unwrap();
+    assert_eq!(e.code(), "validate");
+    assert_eq!(e.message(), "discontinued item can't be ordered");
+    assert!(e.path().is_empty());
+
+    /* once recovered, it's taken */
+    let e = serde_json::from_str::<Item>(r#"{"sku":"serdev","quantity":0}"#).unwrap_err();
+    assert!(serdev::Error::recover(&e).is_some());
+    assert!(serdev::Error::recover(&e).is_none());
+
+    /* structural errors are not validation errors */
+    let e = serde_json::from_str::<Item>(r#"{"sku":"serdev"}"#).unwrap_err();
+    assert!(serdev::Error::recover(&e).is_none());
+
+    /* a plain serde error following a validation failure is not taken as a validation error,
+       even if its message happens to start with the failure's one */
+    let e = serde_json::from_str::<Token>(r#""xx_1""#).unwrap_err();
+    assert_eq!(e.to_string(), "invalid");
+    let e = serde_json::from_str::<Label>(r#"{"text":1}"#).unwrap_err();
+    assert_eq!(e.to_string(), "invalid type: integer `1`, expected a borrowed string at line 1 column 9");
+    assert!(serdev::Error::recover(&e).is_none());
+    assert!(serdev::Errors::recover(&e).is_none());
+
+    let e = serde_json::from_str::<Coupon>(r#"{"code":"XX-2024"}"#).unwrap_err();
+    assert_eq!(e.to_string(), "coupon code must start with `SV`");
+    let e = serdev::Error::recover(&e).unwrap();
+    assert_eq!(e.code(), "coupon_prefix");
+    assert_eq!(e.param("prefix"), Some("SV"));
+
+    /* built-in rules' errors are converted into `String` by `From` */
+    let e = serde_json::from_str::<Parcel>(r#"{"weight":0}"#).unwrap_err();
+    assert_eq!(e.to_string(), "weight: must be greater than or equal to 1");
+    let e = serde_json::from_str::<Parcel>(r#"{"weight":31}"#).unwrap_err();
+    assert_eq!(e.to_string(), "31kg is too heavy");
+
+    let e = serde_json::from_str::<Address>(r#"{"zip":"12-345","city":""}"#).unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "zip: length must be equal to 7; zip: must match the pattern `^[0-9]+$`; city: length must be greater than or equal to 1"
+    );
+    let errors = serdev::Errors::recover(&e).unwrap();
+    assert_eq!(
+        errors.iter().map(|e| e.code()).collect::<Vec<_>>(),
+        ["length", "pattern", "length"]
+    );
+}
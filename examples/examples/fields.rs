@@ -0,0 +1,169 @@
+use serdev::Deserialize;
+
+
+fn non_empty(s: &str) -> Result<(), impl std::fmt::Display> {
+    if s.is_empty() {
+        return Err("must not be empty")
+    }
+    Ok(())
+}
+
+fn adult(age: &u8) -> Result<(), impl std::fmt::Display> {
+    if *age < 18 {
+        return Err("must be 18 or older")
+    }
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct User {
+    #[serde(validate = "non_empty")]
+    first_name: String,
+    #[serde(validate = "adult")]
+    age: u8,
+    #[serde(rename = "mail")]
+    #[serde(validate = "non_empty")]
+    email: String,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(validate = "Self::validate")]
+struct Range(
+    #[serde(validate = "adult")]
+    u8,
+    u8,
+);
+impl Range {
+    fn validate(&self) -> Result<(), impl std::fmt::Display> {
+        if self.0 > self.1 {
+            return Err("start must not exceed end")
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+enum Shape {
+    Circle {
+        #[serde(validate(by = "Self::positive", error = "&'static str"))]
+        radius: i32,
+    },
+    Named(
+        #[serde(validate = "non_empty")]
+        String,
+    ),
+}
+impl Shape {
+    fn positive(n: &i32) -> Result<(), &'static str> {
+        if *n <= 0 {
+            return Err("`radius` must be positive")
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Invalid(&'static str);
+impl std::fmt::Display for Invalid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid {}", self.0)
+    }
+}
+
+fn valid_sku(sku: &str) -> Result<(), Invalid> {
+    if !sku.starts_with("SV-") {
+        return Err(Invalid("SKU"))
+    }
+    Ok(())
+}
+
+/// the field's `Invalid` is reported with the field's name as the default `serdev::Error`
+#[derive(Debug, PartialEq, Deserialize)]
+struct Product {
+    #[serde(validate(by = "valid_sku", error = "Invalid"))]
+    sku: String,
+}
+
+/// the field's `Invalid` is passed through as it is to the container's `Invalid`
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(validate(by = "Self::validate", error = "Invalid"))]
+struct Stock {
+    #[serde(validate(by = "valid_sku", error = "Invalid"))]
+    sku: String,
+    count: u32,
+}
+impl Stock {
+    fn validate(&self) -> Result<(), Invalid> {
+        if self.count > 1000 {
+            return Err(Invalid("count"))
+        }
+        Ok(())
+    }
+}
+
+fn main() {
+    assert_eq!(
+        serde_json::from_str::<User>(
+            r#"{"firstName":"serdev","age":20,"mail":"serdev@ohkami.rs"}"#
+        ).unwrap(),
+        User {
+            first_name: String::from("serdev"),
+            age:        20,
+            email:      String::from("serdev@ohkami.rs")
+        }
+    );
+    assert_eq!(
+        serde_json::from_str::<User>(
+            r#"{"firstName":"","age":20,"mail":"serdev@ohkami.rs"}"#
+        ).unwrap_err().to_string(),
+        "firstName: must not be empty"
+    );
+    assert_eq!(
+        serde_json::from_str::<User>(
+            r#"{"firstName":"serdev","age":2,"mail":"serdev@ohkami.rs"}"#
+        ).unwrap_err().to_string(),
+        "age: must be 18 or older"
+    );
+    assert_eq!(
+        serde_json::from_str::<User>(
+            r#"{"firstName":"serdev","age":20,"mail":""}"#
+        ).unwrap_err().to_string(),
+        "mail: must not be empty"
+    );
+
+    assert_eq!(
+        serde_json::from_str::<Range>(r#"[18,20]"#).unwrap(),
+        Range(18, 20)
+    );
+    assert_eq!(
+        serde_json::from_str::<Range>(r#"[2,20]"#).unwrap_err().to_string(),
+        "0: must be 18 or older"
+    );
+    assert_eq!(
+        serde_json::from_str::<Range>(r#"[20,18]"#).unwrap_err().to_string(),
+        "start must not exceed end"
+    );
+
+    assert_eq!(
+        serde_json::from_str::<Shape>(r#"{"Circle":{"radius":1}}"#).unwrap(),
+        Shape::Circle { radius: 1 }
+    );
+    assert_eq!(
+        serde_json::from_str::<Shape>(r#"{"Circle":{"radius":0}}"#).unwrap_err().to_string(),
+        "Circle.radius: `radius` must be positive"
+    );
+    assert_eq!(
+        serde_json::from_str::<Shape>(r#"{"Named":""}"#).unwrap_err().to_string(),
+        "Named.0: must not be empty"
+    );
+
+    assert_eq!(
+        serde_json::from_str::<Product>(r#"{"sku":"XX-1"}"#).unwrap_err().to_string(),
+        "sku: invalid SKU"
+    );
+    assert_eq!(
+        serde_json::from_str::<Stock>(r#"{"sku":"XX-1","count":1}"#).unwrap_err().to_string(),
+        "invalid SKU"
+    );
+}
@@ -0,0 +1,77 @@
+use serdev::Deserialize;
+use std::collections::HashMap;
+
+
+#[derive(Debug, Deserialize)]
+#[serde(finalize = "Self::index")]
+#[serde(validate = "Self::validate")]
+struct Catalog {
+    #[serdev(length(min = 1))]
+    products: Vec<Product>,
+    #[serde(skip)]
+    by_sku:   HashMap<String, usize>,
+}
+impl Catalog {
+    fn validate(&self) -> Result<(), impl std::fmt::Display> {
+        for (i, product) in self.products.iter().enumerate() {
+            if self.products[..i].iter().any(|p| p.sku == product.sku) {
+                return Err(format!("duplicate sku `{}`", product.sku))
+            }
+        }
+        Ok(())
+    }
+
+    fn index(&mut self) {
+        self.by_sku = self.products.iter().enumerate()
+            .map(|(i, p)| (p.sku.clone(), i))
+            .collect()
+    }
+
+    fn get(&self, sku: &str) -> Option<&Product> {
+        self.by_sku.get(sku).map(|&i| &self.products[i])
+    }
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Product {
+    sku:   String,
+    price: u32,
+}
+
+/// finalized by value
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(finalize = "with_parts")]
+struct Version {
+    #[serdev(trim)]
+    raw:   String,
+    #[serde(skip)]
+    parts: Vec<u32>,
+}
+
+fn with_parts(version: Version) -> Version {
+    let parts = version.raw.split('.').filter_map(|p| p.parse().ok()).collect();
+    Version { parts, ..version }
+}
+
+fn main() {
+    let catalog = serde_json::from_str::<Catalog>(r#"{"products":[
+        {"sku":"ABC-1","price":100},
+        {"sku":"ABC-2","price":250}
+    ]}"#).unwrap();
+    assert_eq!(catalog.get("ABC-2"), Some(&Product { sku: String::from("ABC-2"), price: 250 }));
+    assert_eq!(catalog.get("ABC-3"), None);
+
+    assert_eq!(
+        serde_json::from_str::<Catalog>(r#"{"products":[
+            {"sku":"ABC-1","price":100},
+            {"sku":"ABC-1","price":250}
+        ]}"#).unwrap_err().to_string(),
+        "duplicate sku `ABC-1`"
+    );
+
+    /* skipped fields are never read from input */
+    assert_eq!(
+        serde_json::from_str::<Version>(r#"{"raw":" 1.2.3 ","parts":[9]}"#).unwrap(),
+        Version { raw: String::from("1.2.3"), parts: vec![1, 2, 3] }
+    );
+}
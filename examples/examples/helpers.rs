@@ -0,0 +1,58 @@
+use serdev::Deserialize;
+
+/* helper attributes of other derives, like `#[error]` and `#[source]` of `thiserror::Error`,
+   are not put on the proxy that only derives serde's ones */
+
+#[derive(Debug, Deserialize, thiserror::Error)]
+#[error("{service}: {message}")]
+struct Upstream {
+    #[serdev(length(min = 1))]
+    service: String,
+    message: String,
+}
+
+#[derive(Debug, Deserialize, thiserror::Error)]
+#[serde(rename_all = "snake_case")]
+enum Failure {
+    #[error("timed out after {seconds}s")]
+    Timeout {
+        #[serdev(range(min = 1, max = 3600))]
+        seconds: u32,
+    },
+    #[error("upstream failed")]
+    Upstream {
+        #[source]
+        cause: Upstream,
+    },
+}
+
+/// The docs of a request to create a user
+#[derive(Debug, Deserialize)]
+#[serdev(proxy_keep_attrs(doc))]
+struct CreateUser {
+    /// The name of the user, not empty
+    #[serdev(length(min = 1))]
+    name: String,
+}
+
+fn main() {
+    use std::error::Error as _;
+
+    let failure = serde_json::from_str::<Failure>(r#"{"upstream":{"cause":{"service":"db","message":"down"}}}"#).unwrap();
+    assert_eq!(failure.to_string(), "upstream failed");
+    assert_eq!(failure.source().unwrap().to_string(), "db: down");
+
+    assert_eq!(
+        serde_json::from_str::<Failure>(r#"{"timeout":{"seconds":0}}"#).unwrap_err().to_string(),
+        "timeout.seconds: must be greater than or equal to 1"
+    );
+    assert_eq!(
+        serde_json::from_str::<Failure>(r#"{"upstream":{"cause":{"service":"","message":"down"}}}"#).unwrap_err().to_string(),
+        "upstream.cause.service: length must be greater than or equal to 1 at line 1 column 53"
+    );
+
+    assert_eq!(
+        serde_json::from_str::<CreateUser>(r#"{"name":"serdev"}"#).unwrap().name,
+        "serdev"
+    );
+}
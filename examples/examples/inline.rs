@@ -0,0 +1,58 @@
+use serdev::{Deserialize, Validate};
+
+
+#[derive(Debug, Deserialize)]
+#[serde(validate(expr = "self.start <= self.end", message = "start must not be after end"))]
+struct Range {
+    start: i32,
+    end:   i32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serdev(collect_errors)]
+#[serde(validate(
+    expr = "self.min_players <= self.max_players", message = "min_players must not exceed max_players",
+    expr = "self.title.len() <= 64",
+))]
+struct Game {
+    #[serde(validate(expr = "!title.trim().is_empty()", message = "must not be blank"))]
+    title:       String,
+    min_players: u8,
+    max_players: u8,
+}
+
+#[derive(Debug, Deserialize)]
+enum Window {
+    #[serde(validate(expr = "from < to", message = "must be a non-empty window"))]
+    Between { from: u32, to: u32 },
+    Always,
+}
+
+fn main() {
+    assert!(serde_json::from_str::<Range>(r#"{"start":1,"end":2}"#).is_ok());
+    assert_eq!(
+        serde_json::from_str::<Range>(r#"{"start":3,"end":2}"#).unwrap_err().to_string(),
+        "start must not be after end"
+    );
+
+    let game = serde_json::from_str::<Game>(r#"{"title":"chess","min_players":2,"max_players":2}"#).unwrap();
+    assert!(game.validate().is_ok());
+    assert_eq!(
+        serde_json::from_str::<Game>(r#"{"title":" ","min_players":4,"max_players":2}"#).unwrap_err().to_string(),
+        "title: must not be blank; min_players must not exceed max_players"
+    );
+    let game = Game { title: "x".repeat(65), min_players: 1, max_players: 1 };
+    assert_eq!(game.validate().unwrap_err().to_string(), "must satisfy `self.title.len() <= 64`");
+    let game = Game { title: "x".repeat(65), min_players: 2, max_players: 1 };
+    assert_eq!(
+        game.validate().unwrap_err().to_string(),
+        "min_players must not exceed max_players; must satisfy `self.title.len() <= 64`"
+    );
+
+    assert!(serde_json::from_str::<Window>(r#"{"Between":{"from":1,"to":2}}"#).is_ok());
+    assert!(serde_json::from_str::<Window>(r#""Always""#).is_ok());
+    assert_eq!(
+        serde_json::from_str::<Window>(r#"{"Between":{"from":2,"to":2}}"#).unwrap_err().to_string(),
+        "Between: must be a non-empty window"
+    );
+}
@@ -0,0 +1,87 @@
+use serdev::Deserialize;
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap};
+
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Post<'p> {
+    #[serdev(length(min = 1, max = 8))]
+    title: String,
+    #[serdev(length(max = 6, unit = "bytes"))]
+    slug: &'p str,
+    #[serdev(length(max = 2, unit = "graphemes"))]
+    #[serde(borrow)]
+    reaction: Cow<'p, str>,
+    #[serdev(length(min = 1))]
+    tags: Vec<String>,
+    #[serdev(length(max = 2))]
+    meta: HashMap<String, String>,
+    #[serdev(length(equal = 2))]
+    authors: Option<BTreeSet<u32>>,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Code(
+    #[serdev(length(equal = 4, unit = "chars"))]
+    String,
+);
+
+fn main() {
+    assert_eq!(
+        serde_json::from_str::<Post>(r#"{
+            "title": "ウルフ",
+            "slug": "wolf",
+            "reaction": "👨‍👩‍👧🇯🇵",
+            "tags": ["rust"],
+            "meta": {},
+            "authors": null
+        }"#).unwrap(),
+        Post {
+            title:    String::from("ウルフ"),
+            slug:     "wolf",
+            reaction: Cow::Borrowed("👨‍👩‍👧🇯🇵"),
+            tags:     vec![String::from("rust")],
+            meta:     HashMap::new(),
+            authors:  None
+        }
+    );
+
+    let error = |json: &str| serde_json::from_str::<Post>(json).unwrap_err().to_string();
+    assert_eq!(
+        error(r#"{"title":"","slug":"wolf","reaction":"","tags":["rust"],"meta":{},"authors":null}"#),
+        "title: length must be greater than or equal to 1"
+    );
+    assert_eq!(
+        error(r#"{"title":"ウルフウルフウルフ","slug":"wolf","reaction":"","tags":["rust"],"meta":{},"authors":null}"#),
+        "title: length must be less than or equal to 8"
+    );
+    assert_eq!(
+        error(r#"{"title":"wolf","slug":"ウルフ","reaction":"","tags":["rust"],"meta":{},"authors":null}"#),
+        "slug: length must be less than or equal to 6"
+    );
+    assert_eq!(
+        error(r#"{"title":"wolf","slug":"wolf","reaction":"ééé","tags":["rust"],"meta":{},"authors":null}"#),
+        "reaction: length must be less than or equal to 2"
+    );
+    assert_eq!(
+        error(r#"{"title":"wolf","slug":"wolf","reaction":"","tags":[],"meta":{},"authors":null}"#),
+        "tags: length must be greater than or equal to 1"
+    );
+    assert_eq!(
+        error(r#"{"title":"wolf","slug":"wolf","reaction":"","tags":["rust"],"meta":{"a":"","b":"","c":""},"authors":null}"#),
+        "meta: length must be less than or equal to 2"
+    );
+    assert_eq!(
+        error(r#"{"title":"wolf","slug":"wolf","reaction":"","tags":["rust"],"meta":{},"authors":[1,1,1]}"#),
+        "authors: length must be equal to 2"
+    );
+
+    assert_eq!(
+        serde_json::from_str::<Code>(r#""ｗｏｌｆ""#).unwrap(),
+        Code(String::from("ｗｏｌｆ"))
+    );
+    assert_eq!(
+        serde_json::from_str::<Code>(r#""wolves""#).unwrap_err().to_string(),
+        "0: length must be equal to 4"
+    );
+}
@@ -0,0 +1,92 @@
+/* serde's messages for the structural errors of validated types are the same as the plain ones,
+   naming the type itself instead of the proxy serdev deserializes through */
+
+#[allow(dead_code)]
+mod plain {
+    use serdev::__private__::serde::Deserialize;
+
+    #[derive(Deserialize)]
+    #[serde(crate = "serdev::__private__::serde")]
+    pub struct Point { pub x: i32, pub y: i32 }
+
+    #[derive(Deserialize)]
+    #[serde(crate = "serdev::__private__::serde")]
+    pub struct Pair(pub i32, pub i32);
+
+    #[derive(Deserialize)]
+    #[serde(crate = "serdev::__private__::serde")]
+    pub enum Shape { Circle { r: u32 }, Rect(u32, u32) }
+
+    #[derive(Deserialize)]
+    #[serde(crate = "serdev::__private__::serde")]
+    #[serde(tag = "type")]
+    pub enum Event { Click { x: i32 }, Close }
+
+    #[derive(Deserialize)]
+    #[serde(crate = "serdev::__private__::serde")]
+    #[serde(untagged)]
+    pub enum Id { Number(u32), Pair(u32, u32) }
+}
+
+#[allow(dead_code)]
+mod validated {
+    use serdev::Deserialize;
+
+    fn valid<T>(_: &T) -> Result<(), &'static str> {
+        Ok(())
+    }
+
+    #[derive(Deserialize)]
+    #[serde(validate = "valid")]
+    pub struct Point { pub x: i32, pub y: i32 }
+
+    #[derive(Deserialize)]
+    #[serde(validate = "valid")]
+    pub struct Pair(pub i32, pub i32);
+
+    #[derive(Deserialize)]
+    #[serde(validate = "valid")]
+    pub enum Shape { Circle { r: u32 }, Rect(u32, u32) }
+
+    #[derive(Deserialize)]
+    #[serde(validate = "valid")]
+    #[serde(tag = "type")]
+    pub enum Event { Click { x: i32 }, Close }
+
+    #[derive(Deserialize)]
+    #[serde(validate = "valid")]
+    #[serde(untagged)]
+    pub enum Id { Number(u32), Pair(u32, u32) }
+}
+
+macro_rules! assert_same_error {
+    ($type:ident: $($input:literal => $message:literal),* $(,)?) => {$(
+        let plain = serde_json::from_str::<plain::$type>($input).err().unwrap().to_string();
+        let validated = serde_json::from_str::<validated::$type>($input).err().unwrap().to_string();
+        assert_eq!(plain, validated);
+        assert_eq!(validated, $message);
+    )*};
+}
+
+fn main() {
+    assert_same_error!(Point:
+        "1"         => "invalid type: integer `1`, expected struct Point at line 1 column 1",
+        "[1]"       => "invalid length 1, expected struct Point with 2 elements at line 1 column 3",
+        r#"{"x":1}"# => "missing field `y` at line 1 column 7",
+    );
+    assert_same_error!(Pair:
+        "[1]" => "invalid length 1, expected tuple struct Pair with 2 elements at line 1 column 3",
+    );
+    assert_same_error!(Shape:
+        r#"{"Circle":1}"#  => "invalid type: integer `1`, expected struct variant Shape::Circle at line 1 column 11",
+        r#"{"Rect":[1]}"#  => "invalid length 1, expected tuple variant Shape::Rect with 2 elements at line 1 column 11",
+        r#"{"Square":1}"# => "unknown variant `Square`, expected `Circle` or `Rect` at line 1 column 9",
+    );
+    assert_same_error!(Event:
+        "1"                 => "invalid type: integer `1`, expected internally tagged enum Event at line 1 column 1",
+        r#"{"type":"Click"}"# => "missing field `x`",
+    );
+    assert_same_error!(Id:
+        r#""a""# => "data did not match any variant of untagged enum Id",
+    );
+}
@@ -0,0 +1,40 @@
+use serdev::{Deserialize, Valid, Validate};
+
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(validate = "Self::validate")]
+struct Account {
+    #[serdev(length(min = 1))]
+    owner:   String,
+    balance: i64,
+    limit:   i64,
+}
+impl Account {
+    fn validate(&self) -> Result<(), impl std::fmt::Display> {
+        if self.balance < -self.limit {
+            return Err("balance must not exceed the limit")
+        }
+        Ok(())
+    }
+}
+
+fn main() {
+    let mut account = Account { owner: String::from("wolf"), balance: 100, limit: 50 };
+
+    assert_eq!(account.modify(|a| {a.balance -= 120; a.balance}).unwrap(), -20);
+    assert_eq!(
+        account.modify(|a| a.balance -= 100).unwrap_err().to_string(),
+        "balance must not exceed the limit"
+    );
+    assert_eq!(
+        account.modify(|a| a.owner.clear()).unwrap_err().to_string(),
+        "owner: length must be greater than or equal to 1"
+    );
+    /* failed changes are not committed */
+    assert_eq!(account, Account { owner: String::from("wolf"), balance: -20, limit: 50 });
+
+    let mut valid = Valid::new(account).unwrap();
+    assert!(valid.modify(|a| a.limit = 10).is_err());
+    assert!(valid.modify(|a| a.limit = 20).is_ok());
+    assert_eq!(valid.limit, 20);
+}
@@ -0,0 +1,73 @@
+use serdev::{Deserialize, Validate};
+
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(normalize = "Self::normalize")]
+#[serde(validate = "Self::validate")]
+struct Signup {
+    #[serdev(normalize = "normalize_email")]
+    #[serdev(format = "email")]
+    email: String,
+    #[serdev(normalize = "str::make_ascii_lowercase")]
+    #[serdev(length(min = 3))]
+    name:  String,
+    #[serdev(range(max = 120))]
+    age:   u8,
+}
+impl Signup {
+    /// clamp legacy values
+    fn normalize(&mut self) {
+        if self.age == 255 {
+            self.age = 0
+        }
+    }
+    fn validate(&self) -> Result<(), impl std::fmt::Display> {
+        if self.email.starts_with(&self.name) {
+            return Err("email must not start with the name")
+        }
+        Ok(())
+    }
+}
+
+fn normalize_email(email: &mut String) {
+    *email = email.trim().to_lowercase()
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+enum Tag {
+    Name(
+        #[serdev(normalize = "str::make_ascii_uppercase")]
+        String,
+    ),
+    Id {
+        id: u32,
+    },
+}
+
+fn main() {
+    assert_eq!(
+        serde_json::from_str::<Signup>(r#"{"email":"  Wolf@Example.COM ","name":"Serdev","age":255}"#).unwrap(),
+        Signup { email: String::from("wolf@example.com"), name: String::from("serdev"), age: 0 }
+    );
+    assert_eq!(
+        serde_json::from_str::<Signup>(r#"{"email":" Wolf@Example.COM","name":"WOLF","age":20}"#).unwrap_err().to_string(),
+        "email must not start with the name"
+    );
+
+    /* `Validate` checks the value as it is */
+    let signup = Signup { email: String::from(" x@example.com"), name: String::from("serdev"), age: 20 };
+    assert_eq!(
+        <Signup as Validate>::validate(&signup).unwrap_err().to_string(),
+        "email: must be a valid email address"
+    );
+
+    /* normalization alone */
+    assert_eq!(
+        serde_json::from_str::<Tag>(r#"{"Name":"rust"}"#).unwrap(),
+        Tag::Name(String::from("RUST"))
+    );
+    assert_eq!(
+        serde_json::from_str::<Tag>(r#"{"Id":{"id":1}}"#).unwrap(),
+        Tag::Id { id: 1 }
+    );
+}
@@ -0,0 +1,195 @@
+use serdev::Deserialize;
+use std::collections::BTreeMap;
+
+
+/// no validation of its own, but the paths are tracked through it
+#[derive(Debug, PartialEq, Deserialize)]
+struct Order {
+    customer: Customer,
+    items:    Vec<Item>,
+    #[serde(default)]
+    notes:    BTreeMap<String, Vec<Item>>,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Customer {
+    display_name: String,
+    address:      Option<Address>,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serdev(collect_errors)]
+struct Address {
+    #[serdev(length(equal = 7))]
+    zip:  String,
+    #[serdev(length(min = 1))]
+    city: String,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Item {
+    #[serdev(pattern = "^[A-Z]{3}-[0-9]+$")]
+    sku:      String,
+    #[serdev(range(min = 1))]
+    quantity: u32,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Tree {
+    #[serdev(range(max = 9))]
+    value:    u8,
+    children: Vec<Tree>,
+    left:     Option<Box<Tree>>,
+}
+
+/// fields of a variant are under the key they have on the wire
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(tag = "t")]
+enum Internal {
+    #[serde(validate(expr = "x != y", message = "x and y must differ"))]
+    A { #[serdev(range(min = 1))] x: u8, y: u8 },
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(tag = "t", content = "c")]
+enum Adjacent {
+    #[serde(validate(expr = "x != y", message = "x and y must differ"))]
+    A { #[serdev(range(min = 1))] x: u8, y: u8 },
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(untagged)]
+enum Untagged {
+    #[serde(validate(expr = "x != y", message = "x and y must differ"))]
+    A { #[serdev(range(min = 1))] x: u8, y: u8 },
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(validate = "Self::validate")]
+struct Token(String);
+impl Token {
+    fn validate(&self) -> Result<(), impl std::fmt::Display> {
+        Err("")
+    }
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Other {
+    #[serdev(range(min = 1))]
+    y: u8,
+}
+
+fn main() {
+    let order = |json: &str| serde_json::from_str::<Order>(json);
+
+    assert_eq!(
+        order(r#"{
+            "customer": {"displayName":"wolf", "address":{"zip":"1234567","city":"Tokyo"}},
+            "items": [{"sku":"ABC-1","quantity":2}]
+        }"#).unwrap(),
+        Order {
+            customer: Customer {
+                display_name: String::from("wolf"),
+                address: Some(Address { zip: String::from("1234567"), city: String::from("Tokyo") })
+            },
+            items: vec![Item { sku: String::from("ABC-1"), quantity: 2 }],
+            notes: BTreeMap::new(),
+        }
+    );
+
+    /* missing `Option` is still `None` */
+    assert_eq!(
+        order(r#"{"customer":{"displayName":"wolf"},"items":[]}"#).unwrap().customer.address,
+        None
+    );
+
+    let e = order(r#"{
+        "customer": {"displayName":"wolf", "address":{"zip":"123","city":""}},
+        "items": []
+    }"#).unwrap_err();
+    assert!(e.to_string().starts_with(
+        "customer.address.zip: length must be equal to 7; customer.address.city: length must be greater than or equal to 1"
+    ));
+    let errors = serdev::Errors::recover(&e).unwrap();
+    assert_eq!(
+        errors.iter().map(|e| e.path().iter().map(ToString::to_string).collect::<Vec<_>>()).collect::<Vec<_>>(),
+        [["customer", "address", "zip"], ["customer", "address", "city"]]
+    );
+
+    let e = order(r#"{
+        "customer": {"displayName":"wolf"},
+        "items": [{"sku":"ABC-1","quantity":2}, {"sku":"ABC-2","quantity":1}, {"sku":"ABC-3","quantity":0}]
+    }"#).unwrap_err();
+    assert!(e.to_string().starts_with("items[2].quantity: must be greater than or equal to 1"));
+    assert_eq!(
+        serdev::Error::recover(&e).unwrap().path(),
+        [
+            serdev::PathSegment::Field("items".into()),
+            serdev::PathSegment::Index(2),
+            serdev::PathSegment::Field("quantity".into()),
+        ]
+    );
+
+    let e = order(r#"{
+        "customer": {"displayName":"wolf"},
+        "items": [],
+        "notes": {"gift": [{"sku":"ABC-1","quantity":1}, {"sku":"abc","quantity":1}]}
+    }"#).unwrap_err();
+    assert!(e.to_string().starts_with("notes.gift[1].sku: must match the pattern"));
+
+    /* other errors are kept as they are */
+    assert_eq!(
+        order(r#"{"customer":{"displayName":"wolf"},"items":[{"sku":"ABC-1","quantity":-1}]}"#).unwrap_err().to_string(),
+        "invalid value: integer `-1`, expected u32 at line 1 column 72"
+    );
+    assert_eq!(
+        order(r#"1"#).unwrap_err().to_string(),
+        "invalid type: integer `1`, expected struct Order at line 1 column 1"
+    );
+
+    let e = serde_json::from_str::<Tree>(r#"{
+        "value": 1,
+        "children": [
+            {"value":2, "children":[], "left":null},
+            {"value":3, "children":[], "left":{"value":10, "children":[], "left":null}}
+        ],
+        "left": null
+    }"#).unwrap_err();
+    assert!(e.to_string().starts_with("children[1].left.value: must be less than or equal to 9"));
+
+    /* a validation error of an earlier call doesn't take over later errors, even with an empty message */
+    assert_eq!(
+        serde_json::from_str::<Token>(r#""serdev""#).unwrap_err().to_string(),
+        ""
+    );
+    assert_eq!(
+        serde_json::from_str::<Other>(r#"{"y":"str"}"#).unwrap_err().to_string(),
+        "invalid type: string \"str\", expected u8 at line 1 column 10"
+    );
+
+    assert_eq!(
+        serde_json::from_str::<Internal>(r#"{"t":"A","x":0,"y":1}"#).unwrap_err().to_string(),
+        "x: must be greater than or equal to 1"
+    );
+    assert_eq!(
+        serde_json::from_str::<Internal>(r#"{"t":"A","x":1,"y":1}"#).unwrap_err().to_string(),
+        "x and y must differ"
+    );
+    assert!(
+        serde_json::from_str::<Adjacent>(r#"{"t":"A","c":{"x":0,"y":1}}"#).unwrap_err().to_string()
+        .starts_with("c.x: must be greater than or equal to 1")
+    );
+    assert!(
+        serde_json::from_str::<Adjacent>(r#"{"t":"A","c":{"x":1,"y":1}}"#).unwrap_err().to_string()
+        .starts_with("c: x and y must differ")
+    );
+    assert_eq!(
+        serde_json::from_str::<Untagged>(r#"{"x":0,"y":1}"#).unwrap_err().to_string(),
+        "x: must be greater than or equal to 1"
+    );
+    assert_eq!(
+        serde_json::from_str::<Untagged>(r#"{"x":1,"y":1}"#).unwrap_err().to_string(),
+        "x and y must differ"
+    );
+}
@@ -0,0 +1,52 @@
+use serdev::Deserialize;
+
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Account {
+    #[serdev(pattern = "^[a-z0-9_]{3,32}$")]
+    username: String,
+    #[serdev(pattern = r"^\+?[0-9]{6,15}$", length(max = 12))]
+    phone: Option<String>,
+}
+
+/// valid syntax, but too big to compile
+#[derive(Debug, Deserialize)]
+struct Huge {
+    #[serdev(pattern = r"(?:\w{100}){1000}")]
+    #[allow(unused)]
+    text: String,
+}
+
+fn main() {
+    assert_eq!(
+        serde_json::from_str::<Account>(r#"{"username":"serdev_2","phone":"+81901234"}"#).unwrap(),
+        Account {
+            username: String::from("serdev_2"),
+            phone:    Some(String::from("+81901234"))
+        }
+    );
+    assert_eq!(
+        serde_json::from_str::<Account>(r#"{"username":"serdev_2","phone":null}"#).unwrap(),
+        Account {
+            username: String::from("serdev_2"),
+            phone:    None
+        }
+    );
+    assert_eq!(
+        serde_json::from_str::<Account>(r#"{"username":"SerdeV","phone":null}"#).unwrap_err().to_string(),
+        "username: must match the pattern `^[a-z0-9_]{3,32}$`"
+    );
+    assert_eq!(
+        serde_json::from_str::<Account>(r#"{"username":"serdev","phone":"090-1234"}"#).unwrap_err().to_string(),
+        r"phone: must match the pattern `^\+?[0-9]{6,15}$`"
+    );
+    assert_eq!(
+        serde_json::from_str::<Account>(r#"{"username":"serdev","phone":"+8190123456789"}"#).unwrap_err().to_string(),
+        "phone: length must be less than or equal to 12"
+    );
+
+    assert!(
+        serde_json::from_str::<Huge>(r#"{"text":"a"}"#).unwrap_err().to_string()
+            .starts_with(r"text: invalid pattern `(?:\w{100}){1000}`")
+    );
+}
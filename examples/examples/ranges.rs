@@ -0,0 +1,111 @@
+use serdev::Deserialize;
+
+
+const MAX_AGE: u8 = 150;
+const STEP: i32 = 4;
+const BAD_STEP: i32 = -1;
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Profile {
+    #[serdev(range(min = 18, max = MAX_AGE))]
+    age: u8,
+    #[serdev(range(exclusive_min = 0.0, max = 300.0))]
+    height: f32,
+    #[serdev(range(min = -10, exclusive_max = 10), multiple_of = 2)]
+    score: Option<i64>,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(validate = "Self::validate")]
+struct Window(
+    #[serdev(range(max = 100))]
+    usize,
+    #[serdev(multiple_of = 0.5)]
+    f64,
+);
+impl Window {
+    fn validate(&self) -> Result<(), impl std::fmt::Display> {
+        if self.0 == 0 && self.1 != 0. {
+            return Err("empty window must have zero scale")
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Step {
+    #[serdev(multiple_of = STEP)]
+    n: i32,
+}
+
+/// a divisor given by a constant isn't checked in derive, but fails every check
+#[derive(Debug, PartialEq, Deserialize)]
+struct BadStep {
+    #[serdev(multiple_of = BAD_STEP)]
+    n: i32,
+}
+
+fn main() {
+    assert_eq!(
+        serde_json::from_str::<Profile>(r#"{"age":20,"height":170.5,"score":-4}"#).unwrap(),
+        Profile { age: 20, height: 170.5, score: Some(-4) }
+    );
+    assert_eq!(
+        serde_json::from_str::<Profile>(r#"{"age":20,"height":170.5,"score":null}"#).unwrap(),
+        Profile { age: 20, height: 170.5, score: None }
+    );
+    assert_eq!(
+        serde_json::from_str::<Profile>(r#"{"age":17,"height":170.5,"score":null}"#).unwrap_err().to_string(),
+        "age: must be greater than or equal to 18"
+    );
+    assert_eq!(
+        serde_json::from_str::<Profile>(r#"{"age":151,"height":170.5,"score":null}"#).unwrap_err().to_string(),
+        "age: must be less than or equal to 150"
+    );
+    assert_eq!(
+        serde_json::from_str::<Profile>(r#"{"age":20,"height":0.0,"score":null}"#).unwrap_err().to_string(),
+        "height: must be greater than 0"
+    );
+    assert_eq!(
+        serde_json::from_str::<Profile>(r#"{"age":20,"height":170.5,"score":10}"#).unwrap_err().to_string(),
+        "score: must be less than 10"
+    );
+    assert_eq!(
+        serde_json::from_str::<Profile>(r#"{"age":20,"height":170.5,"score":-12}"#).unwrap_err().to_string(),
+        "score: must be greater than or equal to -10"
+    );
+    assert_eq!(
+        serde_json::from_str::<Profile>(r#"{"age":20,"height":170.5,"score":3}"#).unwrap_err().to_string(),
+        "score: must be a multiple of 2"
+    );
+
+    assert_eq!(
+        serde_json::from_str::<Window>(r#"[10,1.5]"#).unwrap(),
+        Window(10, 1.5)
+    );
+    assert_eq!(
+        serde_json::from_str::<Window>(r#"[101,1.5]"#).unwrap_err().to_string(),
+        "0: must be less than or equal to 100"
+    );
+    assert_eq!(
+        serde_json::from_str::<Window>(r#"[10,1.2]"#).unwrap_err().to_string(),
+        "1: must be a multiple of 0.5"
+    );
+    assert_eq!(
+        serde_json::from_str::<Window>(r#"[0,1.5]"#).unwrap_err().to_string(),
+        "empty window must have zero scale"
+    );
+
+    assert_eq!(
+        serde_json::from_str::<Step>(r#"{"n":-2147483648}"#).unwrap(),
+        Step { n: i32::MIN }
+    );
+    assert_eq!(
+        serde_json::from_str::<Step>(r#"{"n":6}"#).unwrap_err().to_string(),
+        "n: must be a multiple of 4"
+    );
+    assert_eq!(
+        serde_json::from_str::<BadStep>(r#"{"n":4}"#).unwrap_err().to_string(),
+        "n: `multiple_of` must be positive, but is -1"
+    );
+}
@@ -0,0 +1,55 @@
+use serdev::Deserialize;
+use std::borrow::Cow;
+
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Profile<'p> {
+    #[serdev(trim, lowercase)]
+    #[serdev(format = "email")]
+    email:    String,
+    #[serdev(collapse_whitespace, trim)]
+    #[serdev(length(min = 1, max = 16))]
+    name:     Box<str>,
+    #[serdev(nfc)]
+    #[serdev(length(equal = 4, unit = "chars"))]
+    city:     String,
+    #[serdev(uppercase)]
+    #[serde(borrow)]
+    country:  Cow<'p, str>,
+    #[serdev(trim)]
+    nickname: Option<String>,
+}
+
+fn main() {
+    assert_eq!(
+        serde_json::from_str::<Profile>(r#"{
+            "email":    "  Wolf@Example.COM\n",
+            "name":     " Ohkami \t Wolf ",
+            "city":     "Mu\u0308nc",
+            "country":  "jp",
+            "nickname": "  kanarus  "
+        }"#).unwrap(),
+        Profile {
+            email:    String::from("wolf@example.com"),
+            name:     Box::from("Ohkami Wolf"),
+            city:     String::from("M\u{fc}nc"),
+            country:  Cow::Borrowed("JP"),
+            nickname: Some(String::from("kanarus")),
+        }
+    );
+
+    /* already sanitized strings are kept borrowed */
+    let profile = serde_json::from_str::<Profile>(r#"{
+        "email":"wolf@example.com", "name":"wolf", "city":"Köln", "country":"JP", "nickname":null
+    }"#).unwrap();
+    assert!(matches!(profile.country, Cow::Borrowed("JP")));
+    assert_eq!(profile.nickname, None);
+
+    /* sanitized before validation */
+    assert_eq!(
+        serde_json::from_str::<Profile>(r#"{
+            "email":"wolf@example.com", "name":"   ", "city":"Köln", "country":"jp", "nickname":null
+        }"#).unwrap_err().to_string(),
+        "name: length must be greater than or equal to 1"
+    );
+}
@@ -0,0 +1,66 @@
+use serdev::Deserialize;
+
+
+mod rules {
+    pub fn point(p: &super::Point) -> Result<(), impl std::fmt::Display> {
+        if p.x < 0 || p.y < 0 {
+            return Err("point must be in the first quadrant")
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(validate = crate::rules::point)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(validate = Self::validate)]
+struct Circle {
+    #[serde(validate = |r: &f64| if *r > 0.0 {Ok(())} else {Err("must be positive")})]
+    radius: f64,
+    center: Point,
+}
+impl Circle {
+    fn validate(&self) -> Result<(), impl std::fmt::Display> {
+        if self.radius > 1000.0 {
+            return Err("circle is too large")
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+enum Span {
+    #[serde(validate(by = |from: &u32, to: &u32| (from <= to).then_some(()).ok_or("must not be reversed")))]
+    Between(u32, u32),
+}
+
+fn main() {
+    assert!(serde_json::from_str::<Point>(r#"{"x":1,"y":2}"#).is_ok());
+    assert_eq!(
+        serde_json::from_str::<Point>(r#"{"x":-1,"y":2}"#).unwrap_err().to_string(),
+        "point must be in the first quadrant"
+    );
+
+    assert!(serde_json::from_str::<Circle>(r#"{"radius":1.0,"center":{"x":0,"y":0}}"#).is_ok());
+    assert_eq!(
+        serde_json::from_str::<Circle>(r#"{"radius":0.0,"center":{"x":0,"y":0}}"#).unwrap_err().to_string(),
+        "radius: must be positive"
+    );
+    assert_eq!(
+        serde_json::from_str::<Circle>(r#"{"radius":2000.0,"center":{"x":0,"y":0}}"#).unwrap_err().to_string(),
+        "circle is too large"
+    );
+    assert!(serde_json::from_str::<Circle>(r#"{"radius":1.0,"center":{"x":-1,"y":0}}"#).unwrap_err().to_string()
+        .starts_with("center: point must be in the first quadrant"));
+
+    assert!(serde_json::from_str::<Span>(r#"{"Between":[1,2]}"#).is_ok());
+    assert_eq!(
+        serde_json::from_str::<Span>(r#"{"Between":[2,1]}"#).unwrap_err().to_string(),
+        "Between: must not be reversed"
+    );
+}
@@ -0,0 +1,71 @@
+use serdev::{Deserialize, Valid, Validate};
+
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(validate = "Self::validate")]
+struct Order {
+    #[serdev(length(min = 1))]
+    items:    Vec<String>,
+    discount: u8,
+}
+impl Order {
+    fn validate(&self) -> Result<(), impl std::fmt::Display> {
+        if self.discount > 50 && self.items.len() < 3 {
+            return Err("large discount needs 3 or more items")
+        }
+        Ok(())
+    }
+}
+
+/// deserialized and validated by manual implementations
+#[derive(Debug)]
+struct Percent(u8);
+impl<'de> Deserialize<'de> for Percent {
+    fn deserialize<D: serdev::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u8::deserialize(deserializer).map(Self)
+    }
+}
+impl Validate for Percent {
+    type Error = String;
+    fn validate(&self) -> Result<(), Self::Error> {
+        if self.0 > 100 {
+            return Err(format!("{} is not a percentage", self.0))
+        }
+        Ok(())
+    }
+}
+
+fn total_items(orders: &[Valid<Order>]) -> usize {
+    orders.iter().map(|order| order.items.len()).sum()
+}
+
+fn main() {
+    let orders = serde_json::from_str::<Vec<Valid<Order>>>(r#"[
+        {"items":["apple"],"discount":0},
+        {"items":["apple","banana","cherry"],"discount":60}
+    ]"#).unwrap();
+    assert_eq!(total_items(&orders), 4);
+
+    assert_eq!(
+        serde_json::from_str::<Valid<Order>>(r#"{"items":[],"discount":0}"#).unwrap_err().to_string(),
+        "items: length must be greater than or equal to 1"
+    );
+
+    let order = Valid::new(Order { items: vec![String::from("apple")], discount: 10 }).unwrap();
+    assert_eq!(order.discount, 10);
+    assert_eq!(
+        order.into_inner(),
+        Order { items: vec![String::from("apple")], discount: 10 }
+    );
+
+    assert_eq!(
+        Valid::new(Order { items: vec![String::from("apple")], discount: 60 }).unwrap_err().to_string(),
+        "large discount needs 3 or more items"
+    );
+
+    assert_eq!(serde_json::from_str::<Valid<Percent>>("42").unwrap().0, 42);
+    assert_eq!(
+        serde_json::from_str::<Valid<Percent>>("142").unwrap_err().to_string(),
+        "142 is not a percentage"
+    );
+}
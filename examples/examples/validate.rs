@@ -0,0 +1,89 @@
+use serdev::{Deserialize, Serialize, Validate};
+
+
+#[derive(Debug, Deserialize)]
+#[serde(validate = "Self::validate")]
+struct Range {
+    #[serdev(range(min = 0))]
+    start: i32,
+    end:   i32,
+}
+impl Range {
+    fn validate(&self) -> Result<(), impl std::fmt::Display> {
+        if self.start > self.end {
+            return Err("start must not be after end")
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serdev(collect_errors)]
+enum Shape {
+    Circle {
+        #[serdev(range(exclusive_min = 0.0))]
+        radius: f64,
+    },
+    Rect(
+        #[serdev(range(exclusive_min = 0.0))]
+        f64,
+        #[serdev(range(exclusive_min = 0.0))]
+        f64,
+    ),
+}
+
+/// not deserialized, but constructed in code and serialized
+#[derive(Serialize, Validate)]
+#[serde(validate(by = "Self::check", error = "&'static str"))]
+struct Report {
+    title: String,
+    pages: usize,
+}
+impl Report {
+    fn check(&self) -> Result<(), &'static str> {
+        if self.pages == 0 {
+            return Err("report must have some pages")
+        }
+        Ok(())
+    }
+}
+
+#[derive(Validate)]
+struct Unchecked {
+    #[allow(unused)]
+    note: String,
+}
+
+fn main() {
+    let mut range = serde_json::from_str::<Range>(r#"{"start":1,"end":3}"#).unwrap();
+    assert!(range.validate().is_ok());
+
+    /* the inherent `validate` given to `#[serde(validate)]` is still callable as it is */
+    range.end = 0;
+    assert_eq!(Range::validate(&range).unwrap_err().to_string(), "start must not be after end");
+    assert_eq!(<Range as Validate>::validate(&range).unwrap_err().to_string(), "start must not be after end");
+
+    range.start = -1;
+    let e = <Range as Validate>::validate(&range).unwrap_err();
+    assert_eq!(e.to_string(), "start: must be greater than or equal to 0");
+    assert_eq!(e.code(), "range");
+
+    let mut shape = Shape::Circle { radius: 1.0 };
+    assert!(shape.validate().is_ok());
+    if let Shape::Circle { radius } = &mut shape {
+        *radius = 0.0
+    }
+    assert_eq!(shape.validate().unwrap_err().to_string(), "Circle.radius: must be greater than 0");
+    assert_eq!(
+        Shape::Rect(0.0, -1.0).validate().unwrap_err().to_string(),
+        "Rect.0: must be greater than 0; Rect.1: must be greater than 0"
+    );
+
+    let mut report = Report { title: String::from("serdev"), pages: 1 };
+    assert_eq!(report.validate(), Ok(()));
+    assert_eq!(serde_json::to_string(&report).unwrap(), r#"{"title":"serdev","pages":1}"#);
+    report.pages = 0;
+    assert_eq!(report.validate(), Err("report must have some pages"));
+
+    assert!(Unchecked { note: String::new() }.validate().is_ok());
+}
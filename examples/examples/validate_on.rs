@@ -0,0 +1,72 @@
+use serdev::{Deserialize, Serialize, Valid, Validate};
+
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serdev(validate_on = "both")]
+pub struct Payment {
+    #[serdev(range(min = 1))]
+    pub amount:   u64,
+    #[serdev(length(equal = 3))]
+    pub currency: String,
+}
+
+/// accepts legacy inputs, but never emits invalid ones
+#[derive(Debug, Serialize, Deserialize)]
+#[serdev(validate_on = "serialize")]
+#[serde(validate = "Self::validate")]
+struct Event {
+    name: String,
+    at:   u64,
+}
+impl Event {
+    fn validate(&self) -> Result<(), impl std::fmt::Display> {
+        if self.name.is_empty() {
+            return Err("event name must not be empty")
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Validate)]
+#[serdev(validate_on = "serialize")]
+enum Page<T> {
+    Items {
+        #[serdev(length(max = 2))]
+        items: Vec<T>,
+    },
+    Empty,
+}
+
+fn main() {
+    let mut payment = serde_json::from_str::<Payment>(r#"{"amount":100,"currency":"JPY"}"#).unwrap();
+    assert_eq!(serde_json::to_string(&payment).unwrap(), r#"{"amount":100,"currency":"JPY"}"#);
+    payment.amount = 0;
+    assert_eq!(serde_json::to_string(&payment).unwrap_err().to_string(), "amount: must be greater than or equal to 1");
+    assert_eq!(
+        serde_json::from_str::<Payment>(r#"{"amount":100,"currency":"YEN!"}"#).unwrap_err().to_string(),
+        "currency: length must be equal to 3"
+    );
+
+    let event = serde_json::from_str::<Event>(r#"{"name":"","at":0}"#).unwrap();
+    assert_eq!(serde_json::to_string(&event).unwrap_err().to_string(), "event name must not be empty");
+    assert_eq!(
+        serde_json::to_string(&Event { name: String::from("launch"), at: 1 }).unwrap(),
+        r#"{"name":"launch","at":1}"#
+    );
+
+    /* `Valid<T>` still proves the validation */
+    assert_eq!(
+        serde_json::from_str::<Valid<Event>>(r#"{"name":"","at":0}"#).unwrap_err().to_string(),
+        "event name must not be empty"
+    );
+
+    assert_eq!(
+        serde_json::to_string(&Page::Items { items: vec![1, 2] }).unwrap(),
+        r#"{"Items":{"items":[1,2]}}"#
+    );
+    assert_eq!(
+        serde_json::to_string(&Page::Items { items: vec![1, 2, 3] }).unwrap_err().to_string(),
+        "Items.items: length must be less than or equal to 2"
+    );
+    assert_eq!(serde_json::to_string(&Page::<u8>::Empty).unwrap(), r#""Empty""#);
+}
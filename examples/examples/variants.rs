@@ -0,0 +1,96 @@
+use serdev::Deserialize;
+
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Filter {
+    #[serde(validate = "Self::validate_range")]
+    NumberRange {
+        lo: i32,
+        hi: i32,
+    },
+    #[serde(validate = "Self::validate_prefix")]
+    Prefix {
+        prefix: String,
+    },
+    All,
+}
+impl Filter {
+    fn validate_range(lo: &i32, hi: &i32) -> Result<(), impl std::fmt::Display> {
+        if lo > hi {
+            return Err(format!("lo ({lo}) must not exceed hi ({hi})"))
+        }
+        Ok(())
+    }
+
+    fn validate_prefix(prefix: &str) -> Result<(), impl std::fmt::Display> {
+        if prefix.is_empty() {
+            return Err("prefix must not be empty")
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(validate = "Self::validate")]
+enum Pair {
+    #[serde(validate(by = "Self::validate_ordered", error = "&'static str"))]
+    Ordered(u8, u8),
+    Any(u8, u8),
+}
+impl Pair {
+    fn validate_ordered(a: &u8, b: &u8) -> Result<(), &'static str> {
+        if a > b {
+            return Err("`Ordered` pair must be in order")
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), impl std::fmt::Display> {
+        let (Self::Ordered(a, b) | Self::Any(a, b)) = self;
+        if a == b {
+            return Err("pair must have different values")
+        }
+        Ok(())
+    }
+}
+
+fn main() {
+    assert_eq!(
+        serde_json::from_str::<Filter>(r#"{"type":"number_range","lo":1,"hi":2}"#).unwrap(),
+        Filter::NumberRange { lo: 1, hi: 2 }
+    );
+    assert_eq!(
+        serde_json::from_str::<Filter>(r#"{"type":"number_range","lo":3,"hi":2}"#).unwrap_err().to_string(),
+        "lo (3) must not exceed hi (2)"
+    );
+    assert_eq!(
+        serde_json::from_str::<Filter>(r#"{"type":"prefix","prefix":"se"}"#).unwrap(),
+        Filter::Prefix { prefix: String::from("se") }
+    );
+    assert_eq!(
+        serde_json::from_str::<Filter>(r#"{"type":"prefix","prefix":""}"#).unwrap_err().to_string(),
+        "prefix must not be empty"
+    );
+    assert_eq!(
+        serde_json::from_str::<Filter>(r#"{"type":"all"}"#).unwrap(),
+        Filter::All
+    );
+
+    assert_eq!(
+        serde_json::from_str::<Pair>(r#"{"Ordered":[1,2]}"#).unwrap(),
+        Pair::Ordered(1, 2)
+    );
+    assert_eq!(
+        serde_json::from_str::<Pair>(r#"{"Ordered":[2,1]}"#).unwrap_err().to_string(),
+        "Ordered: `Ordered` pair must be in order"
+    );
+    assert_eq!(
+        serde_json::from_str::<Pair>(r#"{"Any":[2,1]}"#).unwrap(),
+        Pair::Any(2, 1)
+    );
+    assert_eq!(
+        serde_json::from_str::<Pair>(r#"{"Any":[1,1]}"#).unwrap_err().to_string(),
+        "pair must have different values"
+    );
+}
@@ -0,0 +1,256 @@
+use std::{borrow::Cow, cell::RefCell, collections::BTreeMap, fmt};
+
+
+/// Validation error, used by default for `#[serde(validate = "...")]` and the built-in rules.
+///
+/// This is passed to `serde::de::Error::custom` and so formats' errors only contain its `Display`ed message,
+/// but the structured one can be recovered by [`Error::recover`] from the format's error.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    code:    Cow<'static, str>,
+    message: Cow<'static, str>,
+    path:    Vec<PathSegment>,
+    params:  BTreeMap<&'static str, String>,
+}
+
+/// A segment of [`Error::path`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// field or variant name
+    Field(Cow<'static, str>),
+    /// index in a sequence
+    Index(usize),
+}
+
+impl Error {
+    /// Create an error with a machine-readable `code` and a human-readable `message`.
+    ///
+    /// Built-in codes are `"validate"` for validation functions, `"range"`, `"multiple_of"`,
+    /// `"length"`, `"pattern"` and `"format"`.
+    pub fn new(code: impl Into<Cow<'static, str>>, message: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            code:    code.into(),
+            message: message.into(),
+            path:    Vec::new(),
+            params:  BTreeMap::new(),
+        }
+    }
+
+    /// Add a parameter of the violated rule, like `min` of `range`
+    pub fn with_param(mut self, name: &'static str, value: impl fmt::Display) -> Self {
+        self.params.insert(name, value.to_string());
+        self
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Path to the invalid value, empty when the whole value is invalid
+    pub fn path(&self) -> &[PathSegment] {
+        &self.path
+    }
+
+    pub fn params(&self) -> &BTreeMap<&'static str, String> {
+        &self.params
+    }
+
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(String::as_str)
+    }
+
+    /// Recover the `Error` which caused `error`, a format's error like `serde_json::Error`,
+    /// when it was raised in the last deserialization of a serdev type on the current thread.
+    /// This is `None` for the other errors, even if some validation failed in earlier calls.
+    /// For `#[serdev(collect_errors)]`, this returns the first one and [`Errors::recover`] returns all.
+    ///
+    /// ```
+    /// # #[derive(serdev::Deserialize, Debug)]
+    /// # struct User { #[serdev(range(min = 18))] age: u8 }
+    /// let e = serde_json::from_str::<User>(r#"{"age":2}"#).unwrap_err();
+    ///
+    /// let e = serdev::Error::recover(&e).unwrap();
+    /// assert_eq!(e.code(), "range");
+    /// assert_eq!(e.param("min"), Some("18"));
+    /// ```
+    pub fn recover(error: &impl fmt::Display) -> Option<Self> {
+        take_stashed(error)?.into_iter().next()
+    }
+
+    pub(crate) fn at(mut self, segment: PathSegment) -> Self {
+        self.path.insert(0, segment);
+        self
+    }
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Field(name) => f.write_str(name),
+            Self::Index(i)    => write!(f, "[{i}]"),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some((first, rest)) = self.path.split_first() {
+            first.fmt(f)?;
+            for segment in rest {
+                if matches!(segment, PathSegment::Field(_)) {
+                    f.write_str(".")?
+                }
+                segment.fmt(f)?
+            }
+            f.write_str(": ")?
+        }
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// as a message of `"validate"` error
+impl From<&'static str> for Error {
+    fn from(message: &'static str) -> Self {
+        Self::new("validate", message)
+    }
+}
+/// as a message of `"validate"` error
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Self::new("validate", message)
+    }
+}
+/// as its `Display`ed message, for `error = "String"` with the built-in rules.
+/// `Box<dyn std::error::Error + Send + Sync>` is supported by std's `From` for `std::error::Error`s.
+impl From<Error> for String {
+    fn from(error: Error) -> Self {
+        error.to_string()
+    }
+}
+
+/// Validation errors collected by `#[serdev(collect_errors)]`, `Display`ed as joined by `; `
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Errors(Vec<Error>);
+
+impl Errors {
+    pub fn iter(&self) -> std::slice::Iter<'_, Error> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Recover the `Errors` which caused `error` in the same way as [`Error::recover`]
+    pub fn recover(error: &impl fmt::Display) -> Option<Self> {
+        take_stashed(error).map(Self)
+    }
+}
+
+impl<E: Into<Error>> Extend<E> for Errors {
+    fn extend<I: IntoIterator<Item = E>>(&mut self, errors: I) {
+        self.0.extend(errors.into_iter().map(Into::into))
+    }
+}
+
+impl IntoIterator for Errors {
+    type Item = Error;
+    type IntoIter = std::vec::IntoIter<Error>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+impl<'e> IntoIterator for &'e Errors {
+    type Item = &'e Error;
+    type IntoIter = std::slice::Iter<'e, Error>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl fmt::Display for Errors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, e) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?
+            }
+            e.fmt(f)?
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Errors {}
+
+thread_local! {
+    /// the last validation errors and their message
+    static STASH: RefCell<Option<(String, Vec<Error>)>> = const { RefCell::new(None) };
+}
+
+/// errors that can be stashed for `recover`
+pub trait Stash: fmt::Display {
+    fn errors(&self) -> Vec<Error>;
+}
+impl Stash for Error {
+    fn errors(&self) -> Vec<Error> {
+        vec![self.clone()]
+    }
+}
+impl Stash for Errors {
+    fn errors(&self) -> Vec<Error> {
+        self.0.clone()
+    }
+}
+
+pub(crate) fn clear_stash() {
+    STASH.with(|stash| *stash.borrow_mut() = None)
+}
+
+pub(crate) fn stash(errors: &impl Stash) {
+    STASH.with(|stash| *stash.borrow_mut() = Some((errors.to_string(), errors.errors())))
+}
+
+/// formats may append some information like position to the message, so this checks by `starts_with`
+fn take_stashed(error: &impl fmt::Display) -> Option<Vec<Error>> {
+    STASH.with(|stash| {
+        let mut stash = stash.borrow_mut();
+        match &*stash {
+            Some((stashed, _)) if error.to_string().starts_with(stashed) => stash.take().map(|(_, errors)| errors),
+            _ => None
+        }
+    })
+}
+
+/// run `deserialize` with the stash cleared, so that only the validation errors raised in it are taken
+/// as the cause of its error, and prefix their paths by `segment`
+pub(crate) fn nest<T, E: serde::de::Error>(
+    deserialize: impl FnOnce() -> Result<T, E>,
+    segment:     impl FnOnce() -> PathSegment,
+) -> Result<T, E> {
+    clear_stash();
+    deserialize().map_err(|error| prefix(error, segment))
+}
+
+/// if `error` is caused by the stashed errors, prefix their paths by `segment`
+/// and replace `error` with the new message
+pub(crate) fn prefix<E: serde::de::Error>(error: E, segment: impl FnOnce() -> PathSegment) -> E {
+    match take_stashed(&error) {
+        None => error,
+        Some(errors) => {
+            let segment = segment();
+            let errors = Errors(errors.into_iter().map(|e| e.at(segment.clone())).collect());
+            stash(&errors);
+            E::custom(errors)
+        }
+    }
+}
@@ -1,13 +1,75 @@
 #![cfg_attr(feature="DEBUG", doc = include_str!("../../README.md"))]
 
-pub use serdev_derive::{Serialize, Deserialize};
+pub use serdev_derive::{Serialize, Deserialize, Validate};
 pub use ::serde::ser::{self, Serialize, Serializer};
 pub use ::serde::de::{self, Deserialize, Deserializer};
 
+mod error;
+mod rules;
+mod path;
+mod validate;
+mod valid;
+
+pub use error::{Error, Errors, PathSegment};
+pub use validate::Validate;
+pub use valid::Valid;
+
 #[doc(hidden)]
 pub mod __private__ {
     pub use serdev_derive::consume;
     pub use ::serde;
-    pub type DefaultError = ::std::string::String;
-    pub fn default_error(e: impl std::fmt::Display) -> DefaultError {e.to_string()}
+    pub use crate::rules::*;
+    pub use crate::path::{FieldName, Tracked};
+    use crate::error::{PathSegment, Stash};
+
+    pub type DefaultError  = crate::Error;
+    pub type DefaultErrors = crate::Errors;
+    pub fn default_error(e: impl std::fmt::Display) -> DefaultError {DefaultError::new("validate", e.to_string())}
+    pub fn field_error(field: &'static str, e: impl std::fmt::Display) -> DefaultError {default_error(e).at(PathSegment::Field(field.into()))}
+    pub fn rule_error(field: &'static str, e: DefaultError) -> DefaultError {e.at(PathSegment::Field(field.into()))}
+    pub fn in_variant(variant: &'static str, e: DefaultError) -> DefaultError {e.at(PathSegment::Field(variant.into()))}
+
+    /// `#[serde(finalize = "function")]` by `fn(&mut T)` or `fn(T) -> T`
+    pub trait Finalize<T, By> {
+        fn finalize(self, this: T) -> T;
+    }
+    pub enum ByRef {}
+    pub enum ByValue {}
+    impl<T, F: FnOnce(&mut T)> Finalize<T, ByRef> for F {
+        #[inline]
+        fn finalize(self, mut this: T) -> T {self(&mut this); this}
+    }
+    impl<T, F: FnOnce(T) -> T> Finalize<T, ByValue> for F {
+        #[inline]
+        fn finalize(self, this: T) -> T {self(this)}
+    }
+    #[inline]
+    pub fn finalize<T, By>(this: T, f: impl Finalize<T, By>) -> T {f.finalize(this)}
+
+    /// proxy deserialized with the stash cleared, so that `recover` never takes the errors of earlier calls
+    /// as the cause of the errors in it
+    pub struct Fresh<T>(pub T);
+    impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Fresh<T> {
+        #[inline]
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            crate::error::clear_stash();
+            T::deserialize(deserializer).map(Self)
+        }
+    }
+
+    /// `(&Stashing(&e)).stash()` stashes `e` to be recovered from the format's error
+    /// if it's `serdev::Error(s)`, or does nothing otherwise
+    pub struct Stashing<'e, E>(pub &'e E);
+    pub trait StashSerdevError {
+        fn stash(&self);
+    }
+    impl<E: Stash> StashSerdevError for Stashing<'_, E> {
+        #[inline]
+        fn stash(&self) {crate::error::stash(self.0)}
+    }
+    pub trait StashOther {
+        #[inline]
+        fn stash(&self) {}
+    }
+    impl<E> StashOther for &Stashing<'_, E> {}
 }
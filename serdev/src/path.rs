@@ -0,0 +1,409 @@
+//! Tracking the path to nested values, attached to [`Error::path`](crate::Error::path)
+//! of the errors raised in deserializing them.
+//!
+//! Each field of a generated proxy is deserialized as `Tracked`, which prefixes its name and
+//! wraps the deserializer by `Tracking` to also prefix the indices in sequences and the keys in maps.
+//! `Tracking` only wraps the deserializers given by the format, so that it's never nested.
+
+use crate::error::{self, PathSegment};
+use serde::de::{self, Deserialize, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use std::{fmt, marker::PhantomData};
+
+
+/// name of a field on the wire, and of the variant it's in if any, given to `Tracked` by a marker type
+pub trait FieldName {
+    const NAME:    &'static str;
+    const VARIANT: Option<&'static str>;
+}
+
+/// field of a proxy, deserialized with tracking the path
+pub struct Tracked<T, N>(pub T, pub PhantomData<N>);
+
+impl<T: Default, N> Default for Tracked<T, N> {
+    fn default() -> Self {
+        Self(T::default(), PhantomData)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, N: FieldName> Deserialize<'de> for Tracked<T, N> {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        error::nest(|| T::deserialize(Tracking(deserializer)), || PathSegment::Field(N::NAME.into()))
+            .map(|value| Self(value, PhantomData))
+            .map_err(|e| match N::VARIANT {
+                None          => e,
+                Some(variant) => error::prefix(e, || PathSegment::Field(variant.into()))
+            })
+    }
+}
+
+/// `Deserializer`, `Visitor` or `DeserializeSeed` tracking sequences and maps in the value
+struct Tracking<X>(X);
+
+macro_rules! forward_deserialize {
+    ($($method:ident($($arg:ident: $ty:ty),*);)*) => {$(
+        #[inline]
+        fn $method<V: Visitor<'de>>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, Self::Error> {
+            self.0.$method($($arg,)* visitor)
+        }
+    )*};
+}
+
+macro_rules! forward_visit {
+    ($($method:ident($ty:ty);)*) => {$(
+        #[inline]
+        fn $method<E: de::Error>(self, v: $ty) -> Result<Self::Value, E> {
+            self.0.$method(v)
+        }
+    )*};
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for Tracking<D> {
+    type Error = D::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_any(Tracking(visitor))
+    }
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_option(Tracking(visitor))
+    }
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, name: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_newtype_struct(name, Tracking(visitor))
+    }
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_seq(Tracking(visitor))
+    }
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_tuple(len, Tracking(visitor))
+    }
+    fn deserialize_tuple_struct<V: Visitor<'de>>(self, name: &'static str, len: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_tuple_struct(name, len, Tracking(visitor))
+    }
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_map(Tracking(visitor))
+    }
+
+    /* structs and enums track their fields by themselves if they're serdev's */
+    forward_deserialize! {
+        deserialize_bool();
+        deserialize_i8();
+        deserialize_i16();
+        deserialize_i32();
+        deserialize_i64();
+        deserialize_i128();
+        deserialize_u8();
+        deserialize_u16();
+        deserialize_u32();
+        deserialize_u64();
+        deserialize_u128();
+        deserialize_f32();
+        deserialize_f64();
+        deserialize_char();
+        deserialize_str();
+        deserialize_string();
+        deserialize_bytes();
+        deserialize_byte_buf();
+        deserialize_unit();
+        deserialize_unit_struct(name: &'static str);
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]);
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]);
+        deserialize_identifier();
+        deserialize_ignored_any();
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.0.is_human_readable()
+    }
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for Tracking<V> {
+    type Value = V::Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.expecting(f)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.0.visit_some(Tracking(deserializer))
+    }
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.0.visit_newtype_struct(Tracking(deserializer))
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        self.0.visit_seq(Indexed { seq, index: 0 })
+    }
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        self.0.visit_map(Keyed { map, key: LastKey::default() })
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.0.visit_none()
+    }
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.0.visit_unit()
+    }
+    fn visit_enum<A: de::EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        self.0.visit_enum(data)
+    }
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        self.0.visit_borrowed_str(v)
+    }
+    fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+        self.0.visit_borrowed_bytes(v)
+    }
+    forward_visit! {
+        visit_bool(bool);
+        visit_i8(i8);
+        visit_i16(i16);
+        visit_i32(i32);
+        visit_i64(i64);
+        visit_i128(i128);
+        visit_u8(u8);
+        visit_u16(u16);
+        visit_u32(u32);
+        visit_u64(u64);
+        visit_u128(u128);
+        visit_f32(f32);
+        visit_f64(f64);
+        visit_char(char);
+        visit_str(&str);
+        visit_string(String);
+        visit_bytes(&[u8]);
+        visit_byte_buf(Vec<u8>);
+    }
+}
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for Tracking<S> {
+    type Value = S::Value;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.0.deserialize(Tracking(deserializer))
+    }
+}
+
+struct Indexed<A> {
+    seq:   A,
+    index: usize,
+}
+
+impl<'de, A: SeqAccess<'de>> SeqAccess<'de> for Indexed<A> {
+    type Error = A::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error> {
+        let index = self.index;
+        self.index += 1;
+        error::nest(|| self.seq.next_element_seed(Tracking(seed)), || PathSegment::Index(index))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.seq.size_hint()
+    }
+}
+
+struct Keyed<'de, A> {
+    map: A,
+    key: LastKey<'de>,
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for Keyed<'de, A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error> {
+        self.key.value = KeyValue::Unknown;
+        self.map.next_key_seed(Key(seed, &mut self.key))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Self::Error> {
+        let Self { map, key } = self;
+        error::nest(|| map.next_value_seed(Tracking(seed)), || PathSegment::Field(key.to_string().into()))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.map.size_hint()
+    }
+}
+
+/// the last key of a map, kept as given and written into a string only for the path of an error
+#[derive(Default)]
+struct LastKey<'de> {
+    value: KeyValue<'de>,
+    /// buffer of a transient string key, reused for each key
+    owned: String,
+}
+
+#[derive(Default)]
+enum KeyValue<'de> {
+    #[default]
+    Unknown,
+    Borrowed(&'de str),
+    /// in `LastKey::owned`
+    Owned,
+    Signed(i128),
+    Unsigned(u128),
+    Char(char),
+    Bool(bool),
+}
+
+impl fmt::Display for LastKey<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value {
+            KeyValue::Unknown     => Ok(()),
+            KeyValue::Borrowed(s) => f.write_str(s),
+            KeyValue::Owned       => f.write_str(&self.owned),
+            KeyValue::Signed(n)   => n.fmt(f),
+            KeyValue::Unsigned(n) => n.fmt(f),
+            KeyValue::Char(c)     => c.fmt(f),
+            KeyValue::Bool(b)     => b.fmt(f),
+        }
+    }
+}
+
+/// `DeserializeSeed`, `Deserializer` or `Visitor` keeping a map key as `LastKey`
+struct Key<'k, 'de, X>(X, &'k mut LastKey<'de>);
+
+macro_rules! key_deserialize {
+    ($($method:ident($($arg:ident: $ty:ty),*);)*) => {$(
+        #[inline]
+        fn $method<V: Visitor<'de>>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, Self::Error> {
+            self.0.$method($($arg,)* Key(visitor, self.1))
+        }
+    )*};
+}
+
+macro_rules! key_visit {
+    ($($method:ident($ty:ty) => $variant:ident;)*) => {$(
+        #[inline]
+        fn $method<E: de::Error>(self, v: $ty) -> Result<Self::Value, E> {
+            self.1.value = KeyValue::$variant(v.into());
+            self.0.$method(v)
+        }
+    )*};
+}
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for Key<'_, 'de, S> {
+    type Value = S::Value;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.0.deserialize(Key(deserializer, self.1))
+    }
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for Key<'_, 'de, D> {
+    type Error = D::Error;
+
+    key_deserialize! {
+        deserialize_any();
+        deserialize_bool();
+        deserialize_i8();
+        deserialize_i16();
+        deserialize_i32();
+        deserialize_i64();
+        deserialize_i128();
+        deserialize_u8();
+        deserialize_u16();
+        deserialize_u32();
+        deserialize_u64();
+        deserialize_u128();
+        deserialize_f32();
+        deserialize_f64();
+        deserialize_char();
+        deserialize_str();
+        deserialize_string();
+        deserialize_bytes();
+        deserialize_byte_buf();
+        deserialize_option();
+        deserialize_unit();
+        deserialize_unit_struct(name: &'static str);
+        deserialize_newtype_struct(name: &'static str);
+        deserialize_seq();
+        deserialize_tuple(len: usize);
+        deserialize_tuple_struct(name: &'static str, len: usize);
+        deserialize_map();
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]);
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]);
+        deserialize_identifier();
+        deserialize_ignored_any();
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.0.is_human_readable()
+    }
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for Key<'_, 'de, V> {
+    type Value = V::Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.expecting(f)
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        self.1.value = KeyValue::Borrowed(v);
+        self.0.visit_borrowed_str(v)
+    }
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        self.1.owned.clear();
+        self.1.owned.push_str(v);
+        self.1.value = KeyValue::Owned;
+        self.0.visit_str(v)
+    }
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        self.1.owned.clear();
+        self.1.owned.push_str(&v);
+        self.1.value = KeyValue::Owned;
+        self.0.visit_string(v)
+    }
+    key_visit! {
+        visit_bool(bool) => Bool;
+        visit_i8(i8)     => Signed;
+        visit_i16(i16)   => Signed;
+        visit_i32(i32)   => Signed;
+        visit_i64(i64)   => Signed;
+        visit_i128(i128) => Signed;
+        visit_u8(u8)     => Unsigned;
+        visit_u16(u16)   => Unsigned;
+        visit_u32(u32)   => Unsigned;
+        visit_u64(u64)   => Unsigned;
+        visit_u128(u128) => Unsigned;
+        visit_char(char) => Char;
+    }
+
+    fn visit_f32<E: de::Error>(self, v: f32) -> Result<Self::Value, E> {
+        self.0.visit_f32(v)
+    }
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+        self.0.visit_f64(v)
+    }
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        self.0.visit_bytes(v)
+    }
+    fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+        self.0.visit_borrowed_bytes(v)
+    }
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        self.0.visit_byte_buf(v)
+    }
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.0.visit_none()
+    }
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.0.visit_some(deserializer)
+    }
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.0.visit_unit()
+    }
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.0.visit_newtype_struct(deserializer)
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        self.0.visit_seq(seq)
+    }
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        self.0.visit_map(map)
+    }
+    fn visit_enum<A: de::EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        self.0.visit_enum(data)
+    }
+}
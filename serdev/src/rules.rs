@@ -0,0 +1,60 @@
+//! runtime of the built-in rules like `#[serdev(range(min = 0))]` and sanitizers like `#[serdev(trim)]`
+
+mod number;
+mod length;
+mod format;
+mod sanitize;
+#[cfg(feature = "regex")]
+mod pattern;
+
+pub use self::number::*;
+pub use self::length::*;
+pub use self::format::*;
+pub use self::sanitize::*;
+#[cfg(feature = "regex")]
+pub use self::pattern::*;
+
+use std::borrow::{Cow, ToOwned};
+
+
+/// a string or an optional string, where `None` passes every check
+#[diagnostic::on_unimplemented(message = "`pattern` and `format` can't check `{Self}`", label = "`{Self}` is not a string")]
+pub trait Text {
+    fn text(&self) -> Option<&str>;
+}
+impl Text for str {
+    #[inline]
+    fn text(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+impl Text for String {
+    #[inline]
+    fn text(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+impl<T: Text + ?Sized> Text for &T {
+    #[inline]
+    fn text(&self) -> Option<&str> {
+        (**self).text()
+    }
+}
+impl<T: Text + ?Sized> Text for Box<T> {
+    #[inline]
+    fn text(&self) -> Option<&str> {
+        (**self).text()
+    }
+}
+impl<T: Text + ToOwned + ?Sized> Text for Cow<'_, T> {
+    #[inline]
+    fn text(&self) -> Option<&str> {
+        (**self).text()
+    }
+}
+impl<T: Text> Text for Option<T> {
+    #[inline]
+    fn text(&self) -> Option<&str> {
+        self.as_ref()?.text()
+    }
+}
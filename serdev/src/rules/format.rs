@@ -0,0 +1,147 @@
+use super::Text;
+use crate::Error;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+
+macro_rules! formats {
+    ($( $check:ident: $is_valid:ident ($format:literal) => $message:literal; )*) => {$(
+        #[inline]
+        pub fn $check<T: Text + ?Sized>(value: &T) -> Result<(), Error> {
+            match value.text() {
+                Some(text) if !$is_valid(text) => Err(
+                    Error::new("format", $message).with_param("format", $format)
+                ),
+                _ => Ok(())
+            }
+        }
+    )*};
+}
+formats! {
+    format_email:    is_email    ("email")    => "must be a valid email address";
+    format_url:      is_url      ("url")      => "must be a valid URL";
+    format_uuid:     is_uuid     ("uuid")     => "must be a valid UUID";
+    format_ipv4:     is_ipv4     ("ipv4")     => "must be a valid IPv4 address";
+    format_ipv6:     is_ipv6     ("ipv6")     => "must be a valid IPv6 address";
+    format_hostname: is_hostname ("hostname") => "must be a valid hostname";
+}
+
+fn is_ipv4(text: &str) -> bool {
+    text.parse::<Ipv4Addr>().is_ok()
+}
+
+fn is_ipv6(text: &str) -> bool {
+    text.parse::<Ipv6Addr>().is_ok()
+}
+
+/// RFC 1123 hostname: dot-separated labels of ASCII alphanumerics and inner `-`s
+fn is_hostname(text: &str) -> bool {
+    fn is_label(label: &str) -> bool {
+        (1..=63).contains(&label.len())
+        && label.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        && !label.starts_with('-')
+        && !label.ends_with('-')
+    }
+
+    (1..=253).contains(&text.len()) && text.split('.').all(is_label)
+}
+
+/// 8-4-4-4-12 hexadecimal digits, case-insensitive
+fn is_uuid(text: &str) -> bool {
+    text.len() == 36 && text.bytes().enumerate().all(|(i, b)| match i {
+        8 | 13 | 18 | 23 => b == b'-',
+        _ => b.is_ascii_hexdigit()
+    })
+}
+
+/// `local@domain`, where `local` is a dot-atom of RFC 5322 and `domain`
+/// is a hostname or an address literal like `[192.0.2.1]` or `[IPv6:2001:db8::1]`
+fn is_email(text: &str) -> bool {
+    fn is_local(local: &str) -> bool {
+        (1..=64).contains(&local.len()) && local.split('.').all(|atom| {
+            !atom.is_empty() && atom.bytes().all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-/=?^_`{|}~".contains(&b))
+        })
+    }
+
+    fn is_domain(domain: &str) -> bool {
+        match domain.strip_prefix('[').and_then(|d| d.strip_suffix(']')) {
+            Some(literal) => match literal.strip_prefix("IPv6:") {
+                Some(v6) => is_ipv6(v6),
+                None     => is_ipv4(literal)
+            },
+            None => is_hostname(domain)
+        }
+    }
+
+    text.len() <= 254 && match text.rsplit_once('@') {
+        Some((local, domain)) => is_local(local) && is_domain(domain),
+        None => false
+    }
+}
+
+/// absolute URL like `https://user@example.com:8080/path?query#fragment` or `mailto:serdev@ohkami.rs`:
+/// a scheme and, if it has `//`, a valid host with optional user info and port, or an empty authority
+/// only for `file` like `file:///etc/hosts` ( RFC 8089 ), where neither the user info nor the rest
+/// has whitespace, control characters nor malformed percent-encodings
+fn is_url(text: &str) -> bool {
+    fn is_scheme(scheme: &str) -> bool {
+        scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.'))
+    }
+
+    fn is_authority(authority: &str) -> bool {
+        let host_port = match authority.rsplit_once('@') {
+            Some((userinfo, host_port)) if is_rest(userinfo) => host_port,
+            Some(_) => return false,
+            None => authority
+        };
+        let (host, port) = match host_port.rfind(':') {
+            Some(colon) if !host_port[colon..].contains(']') => (&host_port[..colon], &host_port[colon+1..]),
+            _ => (host_port, "")
+        };
+        let is_valid_host = match host.strip_prefix('[').and_then(|h| h.strip_suffix(']')) {
+            Some(v6) => is_ipv6(v6),
+            None     => is_ipv4(host) || is_hostname(host)
+        };
+        let is_valid_port = port.is_empty() || (
+            port.bytes().all(|b| b.is_ascii_digit()) && port.parse::<u16>().is_ok()
+        );
+        is_valid_host && is_valid_port
+    }
+
+    fn is_rest(rest: &str) -> bool {
+        let bytes = rest.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'%' => {
+                    if !bytes.get(i+1..i+3).is_some_and(|hex| hex.iter().all(u8::is_ascii_hexdigit)) {
+                        return false
+                    }
+                    i += 3
+                }
+                b if b.is_ascii_whitespace() || b.is_ascii_control() => return false,
+                _ => i += 1
+            }
+        }
+        true
+    }
+
+    let Some((scheme, rest)) = text.split_once(':') else {
+        return false
+    };
+    if !is_scheme(scheme) || rest.is_empty() {
+        return false
+    }
+    match rest.strip_prefix("//") {
+        Some(hierarchical) => {
+            let authority_end = hierarchical.find(['/', '?', '#']).unwrap_or(hierarchical.len());
+            let (authority, path) = hierarchical.split_at(authority_end);
+            let is_valid_authority = match authority.is_empty() {
+                true  => scheme.eq_ignore_ascii_case("file"),
+                false => is_authority(authority)
+            };
+            is_valid_authority && is_rest(path)
+        }
+        None => is_rest(rest)
+    }
+}
@@ -0,0 +1,227 @@
+use crate::Error;
+use std::borrow::{Cow, ToOwned};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+
+/// the natural length of a value: number of chars for strings, number of elements for collections.
+/// `None` for `Option::None`, which passes every check.
+#[diagnostic::on_unimplemented(message = "`length` can't check `{Self}`", label = "`{Self}` is neither a string nor a collection")]
+pub trait Length {
+    fn length(&self) -> Option<usize>;
+}
+
+/// lengths of a string in other units than the natural one
+#[diagnostic::on_unimplemented(message = "`length` with `unit` can't check `{Self}`", label = "`{Self}` is not a string")]
+pub trait TextLength {
+    fn bytes(&self) -> Option<usize>;
+    fn chars(&self) -> Option<usize>;
+    /// approximate number of user-perceived characters, without full Unicode segmentation:
+    /// combining marks, variation selectors, emoji modifiers and ZWJ sequences are merged
+    /// into the preceding character, as are the second of a regional indicator pair and `\n` after `\r`.
+    fn graphemes(&self) -> Option<usize>;
+}
+
+impl Length for str {
+    #[inline]
+    fn length(&self) -> Option<usize> {
+        TextLength::chars(self)
+    }
+}
+impl TextLength for str {
+    #[inline]
+    fn bytes(&self) -> Option<usize> {
+        Some(self.len())
+    }
+    #[inline]
+    fn chars(&self) -> Option<usize> {
+        Some(str::chars(self).count())
+    }
+    fn graphemes(&self) -> Option<usize> {
+        fn is_extending(c: char) -> bool {
+            matches!(c,
+                '\u{0300}'..='\u{036F}' |
+                '\u{1AB0}'..='\u{1AFF}' |
+                '\u{1DC0}'..='\u{1DFF}' |
+                '\u{200D}'              |
+                '\u{20D0}'..='\u{20FF}' |
+                '\u{FE00}'..='\u{FE0F}' |
+                '\u{FE20}'..='\u{FE2F}' |
+                '\u{1F3FB}'..='\u{1F3FF}' |
+                '\u{E0020}'..='\u{E007F}' |
+                '\u{E0100}'..='\u{E01EF}'
+            )
+        }
+        fn is_regional_indicator(c: char) -> bool {
+            matches!(c, '\u{1F1E6}'..='\u{1F1FF}')
+        }
+
+        let mut count = 0;
+        let mut prev  = None;
+        let mut regional_pending = false;
+        for c in str::chars(self) {
+            let joined = match prev {
+                None => false,
+                Some('\r') => c == '\n',
+                Some('\u{200D}') => true,
+                Some(_) => is_extending(c) || (regional_pending && is_regional_indicator(c))
+            };
+            if !joined {
+                count += 1
+            }
+            regional_pending = is_regional_indicator(c) && !(joined && regional_pending);
+            prev = Some(c);
+        }
+        Some(count)
+    }
+}
+
+impl Length for String {
+    #[inline]
+    fn length(&self) -> Option<usize> {
+        self.as_str().length()
+    }
+}
+impl TextLength for String {
+    #[inline]
+    fn bytes(&self) -> Option<usize> {
+        TextLength::bytes(self.as_str())
+    }
+    #[inline]
+    fn chars(&self) -> Option<usize> {
+        TextLength::chars(self.as_str())
+    }
+    #[inline]
+    fn graphemes(&self) -> Option<usize> {
+        TextLength::graphemes(self.as_str())
+    }
+}
+
+impl<T> Length for [T] {
+    #[inline]
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+impl<T, const N: usize> Length for [T; N] {
+    #[inline]
+    fn length(&self) -> Option<usize> {
+        Some(N)
+    }
+}
+impl<T> Length for Vec<T> {
+    #[inline]
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+impl<T> Length for VecDeque<T> {
+    #[inline]
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+impl<K, V, S> Length for HashMap<K, V, S> {
+    #[inline]
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+impl<K, V> Length for BTreeMap<K, V> {
+    #[inline]
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+impl<T, S> Length for HashSet<T, S> {
+    #[inline]
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+impl<T> Length for BTreeSet<T> {
+    #[inline]
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+macro_rules! delegate {
+    ($( <$($g:lifetime),*> $t:ty $(where T: $bound:path)?; )*) => {$(
+        impl<$($g,)* T: Length + ?Sized $(+ $bound)?> Length for $t {
+            #[inline]
+            fn length(&self) -> Option<usize> {
+                (**self).length()
+            }
+        }
+        impl<$($g,)* T: TextLength + ?Sized $(+ $bound)?> TextLength for $t {
+            #[inline]
+            fn bytes(&self) -> Option<usize> {
+                (**self).bytes()
+            }
+            #[inline]
+            fn chars(&self) -> Option<usize> {
+                (**self).chars()
+            }
+            #[inline]
+            fn graphemes(&self) -> Option<usize> {
+                (**self).graphemes()
+            }
+        }
+    )*};
+}
+delegate! {
+    <> &T;
+    <> Box<T>;
+    <'c> Cow<'c, T> where T: ToOwned;
+}
+
+impl<T: Length> Length for Option<T> {
+    #[inline]
+    fn length(&self) -> Option<usize> {
+        self.as_ref()?.length()
+    }
+}
+impl<T: TextLength> TextLength for Option<T> {
+    #[inline]
+    fn bytes(&self) -> Option<usize> {
+        self.as_ref()?.bytes()
+    }
+    #[inline]
+    fn chars(&self) -> Option<usize> {
+        self.as_ref()?.chars()
+    }
+    #[inline]
+    fn graphemes(&self) -> Option<usize> {
+        self.as_ref()?.graphemes()
+    }
+}
+
+#[inline]
+pub fn length_min<T: ?Sized>(value: &T, measure: impl FnOnce(&T) -> Option<usize>, min: usize) -> Result<(), Error> {
+    match measure(value) {
+        Some(len) if len < min => Err(
+            Error::new("length", format!("length must be greater than or equal to {min}")).with_param("min", min)
+        ),
+        _ => Ok(())
+    }
+}
+
+#[inline]
+pub fn length_max<T: ?Sized>(value: &T, measure: impl FnOnce(&T) -> Option<usize>, max: usize) -> Result<(), Error> {
+    match measure(value) {
+        Some(len) if len > max => Err(
+            Error::new("length", format!("length must be less than or equal to {max}")).with_param("max", max)
+        ),
+        _ => Ok(())
+    }
+}
+
+#[inline]
+pub fn length_equal<T: ?Sized>(value: &T, measure: impl FnOnce(&T) -> Option<usize>, equal: usize) -> Result<(), Error> {
+    match measure(value) {
+        Some(len) if len != equal => Err(
+            Error::new("length", format!("length must be equal to {equal}")).with_param("equal", equal)
+        ),
+        _ => Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+use crate::Error;
+use std::{cmp::Ordering::{Equal, Greater, Less}, fmt::Display};
+
+
+#[diagnostic::on_unimplemented(message = "`range` and `multiple_of` can't check `{Self}`", label = "`{Self}` is not a primitive number")]
+pub trait Number: Copy + PartialOrd + Display {
+    fn is_positive(self) -> bool;
+    /// for positive `n`
+    fn is_multiple_of(self, n: Self) -> bool;
+}
+macro_rules! integer {
+    ($($t:ty)*) => {$(
+        impl Number for $t {
+            #[inline]
+            #[allow(unused_comparisons)]
+            fn is_positive(self) -> bool {
+                self > 0
+            }
+            /// never overflows as `n` is neither `0` nor `-1`
+            #[inline]
+            fn is_multiple_of(self, n: Self) -> bool {
+                self % n == 0
+            }
+        }
+    )*};
+}
+macro_rules! float {
+    ($($t:ty)*) => {$(
+        impl Number for $t {
+            /// `false` for `NaN`
+            #[inline]
+            fn is_positive(self) -> bool {
+                self > 0.
+            }
+            /// by `%`, so it's subject to the rounding errors like `0.3 % 0.1 != 0.`
+            #[inline]
+            fn is_multiple_of(self, n: Self) -> bool {
+                self % n == 0.
+            }
+        }
+    )*};
+}
+integer! { u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize }
+float! { f32 f64 }
+
+/// a number or an optional number, where `None` passes every check.
+/// Incomparable values like `NaN` fail every check.
+#[diagnostic::on_unimplemented(message = "`range` and `multiple_of` can't check `{Self}`", label = "`{Self}` is not a number")]
+pub trait Numeric {
+    type Number: Number;
+    fn number(&self) -> Option<Self::Number>;
+}
+impl<N: Number> Numeric for N {
+    type Number = N;
+    #[inline]
+    fn number(&self) -> Option<Self::Number> {
+        Some(*self)
+    }
+}
+impl<N: Number> Numeric for Option<N> {
+    type Number = N;
+    #[inline]
+    fn number(&self) -> Option<Self::Number> {
+        *self
+    }
+}
+
+#[inline]
+pub fn number_min<T: Numeric>(value: &T, min: T::Number) -> Result<(), Error> {
+    match value.number() {
+        Some(n) if !matches!(n.partial_cmp(&min), Some(Greater | Equal)) => Err(
+            Error::new("range", format!("must be greater than or equal to {min}")).with_param("min", min)
+        ),
+        _ => Ok(())
+    }
+}
+
+#[inline]
+pub fn number_max<T: Numeric>(value: &T, max: T::Number) -> Result<(), Error> {
+    match value.number() {
+        Some(n) if !matches!(n.partial_cmp(&max), Some(Less | Equal)) => Err(
+            Error::new("range", format!("must be less than or equal to {max}")).with_param("max", max)
+        ),
+        _ => Ok(())
+    }
+}
+
+#[inline]
+pub fn number_exclusive_min<T: Numeric>(value: &T, min: T::Number) -> Result<(), Error> {
+    match value.number() {
+        Some(n) if !matches!(n.partial_cmp(&min), Some(Greater)) => Err(
+            Error::new("range", format!("must be greater than {min}")).with_param("exclusive_min", min)
+        ),
+        _ => Ok(())
+    }
+}
+
+#[inline]
+pub fn number_exclusive_max<T: Numeric>(value: &T, max: T::Number) -> Result<(), Error> {
+    match value.number() {
+        Some(n) if !matches!(n.partial_cmp(&max), Some(Less)) => Err(
+            Error::new("range", format!("must be less than {max}")).with_param("exclusive_max", max)
+        ),
+        _ => Ok(())
+    }
+}
+
+/// fails for a non-positive `n`, which may be given by a constant, whatever the value is
+#[inline]
+pub fn number_multiple_of<T: Numeric>(value: &T, n: T::Number) -> Result<(), Error> {
+    if !n.is_positive() {
+        return Err(
+            Error::new("multiple_of", format!("`multiple_of` must be positive, but is {n}")).with_param("multiple_of", n)
+        )
+    }
+    match value.number() {
+        Some(v) if !v.is_multiple_of(n) => Err(
+            Error::new("multiple_of", format!("must be a multiple of {n}")).with_param("multiple_of", n)
+        ),
+        _ => Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use super::Text;
+use crate::Error;
+use regex::Regex;
+use std::sync::OnceLock;
+
+
+/// a regex compiled at its first use, to be put in a `static`.
+/// The syntax is checked in derive, but building it can still fail like by the size limit.
+pub struct Pattern {
+    source: &'static str,
+    regex:  OnceLock<Result<Regex, regex::Error>>,
+}
+
+impl Pattern {
+    pub const fn new(source: &'static str) -> Self {
+        Self { source, regex: OnceLock::new() }
+    }
+
+    fn regex(&self) -> Result<&Regex, Error> {
+        self.regex.get_or_init(|| Regex::new(self.source)).as_ref().map_err(|e| {
+            Error::new("pattern", format!("invalid pattern `{}`: {e}", self.source)).with_param("pattern", self.source)
+        })
+    }
+}
+
+#[inline]
+pub fn pattern_match<T: Text + ?Sized>(value: &T, pattern: &Pattern) -> Result<(), Error> {
+    match value.text() {
+        Some(text) if !pattern.regex()?.is_match(text) => Err(
+            Error::new("pattern", format!("must match the pattern `{}`", pattern.source)).with_param("pattern", pattern.source)
+        ),
+        _ => Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use std::borrow::Cow;
+
+
+/// a string or an optional string to be sanitized in place, where `None` is left as it is
+#[diagnostic::on_unimplemented(message = "`sanitize` can't apply to `{Self}`", label = "`{Self}` is not a string")]
+pub trait TextMut {
+    /// replace the text with `f`'s result if it's `Some`, meaning the text is changed
+    fn map_text(&mut self, f: impl FnOnce(&str) -> Option<String>);
+}
+impl TextMut for String {
+    #[inline]
+    fn map_text(&mut self, f: impl FnOnce(&str) -> Option<String>) {
+        if let Some(new) = f(self) {*self = new}
+    }
+}
+impl TextMut for Box<str> {
+    #[inline]
+    fn map_text(&mut self, f: impl FnOnce(&str) -> Option<String>) {
+        if let Some(new) = f(self) {*self = new.into_boxed_str()}
+    }
+}
+impl TextMut for Cow<'_, str> {
+    #[inline]
+    fn map_text(&mut self, f: impl FnOnce(&str) -> Option<String>) {
+        if let Some(new) = f(self) {*self = Cow::Owned(new)}
+    }
+}
+impl<T: TextMut> TextMut for Option<T> {
+    #[inline]
+    fn map_text(&mut self, f: impl FnOnce(&str) -> Option<String>) {
+        if let Some(text) = self {text.map_text(f)}
+    }
+}
+
+pub fn sanitize_trim(value: &mut impl TextMut) {
+    value.map_text(|s| {
+        let trimmed = s.trim();
+        (trimmed.len() != s.len()).then(|| trimmed.to_owned())
+    })
+}
+
+pub fn sanitize_lowercase(value: &mut impl TextMut) {
+    value.map_text(|s| {
+        let lower = s.to_lowercase();
+        (lower != s).then_some(lower)
+    })
+}
+
+pub fn sanitize_uppercase(value: &mut impl TextMut) {
+    value.map_text(|s| {
+        let upper = s.to_uppercase();
+        (upper != s).then_some(upper)
+    })
+}
+
+/// Unicode Normalization Form C
+#[cfg(feature = "unicode")]
+pub fn sanitize_nfc(value: &mut impl TextMut) {
+    use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization};
+
+    value.map_text(|s| match is_nfc_quick(s.chars()) {
+        IsNormalized::Yes => None,
+        _ => {
+            let nfc = s.nfc().collect::<String>();
+            (nfc != s).then_some(nfc)
+        }
+    })
+}
+
+/// replace each run of whitespaces with a single space
+pub fn sanitize_collapse_whitespace(value: &mut impl TextMut) {
+    value.map_text(|s| {
+        let mut collapsed = String::with_capacity(s.len());
+        let mut in_whitespace = false;
+        for c in s.chars() {
+            if c.is_whitespace() {
+                if !in_whitespace {collapsed.push(' ')}
+                in_whitespace = true
+            } else {
+                collapsed.push(c);
+                in_whitespace = false
+            }
+        }
+        (collapsed != s).then_some(collapsed)
+    })
+}
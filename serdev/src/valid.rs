@@ -0,0 +1,97 @@
+use crate::Validate;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{fmt, ops::Deref};
+
+
+/// Proof that the value has passed its [`Validate`].
+///
+/// This can only be obtained by [`Valid::new`] or deserialization, and only gives
+/// shared access to the value by `Deref` or validated changes by [`Valid::modify`],
+/// so functions taking `Valid<T>` can rely on the validation rules of `T`.
+///
+/// ```
+/// use serdev::Valid;
+///
+/// #[derive(serdev::Deserialize, Debug)]
+/// struct Order {
+///     #[serdev(range(min = 1))]
+///     quantity: u32,
+/// }
+///
+/// fn ship(order: &Valid<Order>) -> u32 {
+///     order.quantity
+/// }
+///
+/// let order = serde_json::from_str::<Valid<Order>>(r#"{"quantity":2}"#).unwrap();
+/// assert_eq!(ship(&order), 2);
+///
+/// assert!(Valid::new(Order { quantity: 0 }).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Valid<T>(T);
+
+impl<T: Validate> Valid<T> {
+    /// Validate `value` and wrap it if it's valid
+    pub fn new(value: T) -> Result<Self, T::Error> {
+        value.validate()?;
+        Ok(Self(value))
+    }
+}
+
+impl<T: Validate + Clone> Valid<T> {
+    /// Apply `f` to a copy of the value, and commit the change only if it's still valid.
+    /// See [`Validate::modify`].
+    pub fn modify<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> Result<R, T::Error> {
+        self.0.modify(f)
+    }
+}
+
+impl<T> Valid<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Valid<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> AsRef<T> for Valid<T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Valid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: Serialize> Serialize for Valid<T> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+/// runs `validate` only when `T`'s `Deserialize` doesn't
+impl<'de, T> Deserialize<'de> for Valid<T>
+where
+    T: Deserialize<'de> + Validate,
+    T::Error: fmt::Display,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = T::deserialize(deserializer)?;
+        if !T::VALIDATED_IN_DESERIALIZE {
+            value.validate().map_err(de::Error::custom)?
+        }
+        Ok(Self(value))
+    }
+}
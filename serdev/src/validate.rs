@@ -0,0 +1,62 @@
+/// Validation by the rules declared with `#[serde(validate = "...")]` and `#[serdev(...)]`.
+///
+/// `#[derive(serdev::Deserialize)]` implements this for the types having some validation rules,
+/// and runs it in deserialization. `#[derive(serdev::Validate)]` implements this alone for the
+/// types not deriving `serdev::Deserialize`, so don't derive both.
+///
+/// This is useful to re-check values constructed or mutated in code by the exact same rules:
+///
+/// ```
+/// use serdev::Validate;
+///
+/// #[derive(serdev::Deserialize)]
+/// struct User {
+///     #[serdev(range(min = 18))]
+///     age: u8,
+/// }
+///
+/// let mut user = User { age: 20 };
+/// assert!(user.validate().is_ok());
+///
+/// user.age = 2;
+/// assert_eq!(user.validate().unwrap_err().to_string(), "age: must be greater than or equal to 18");
+/// ```
+pub trait Validate {
+    /// `serdev::Error` ( `serdev::Errors` with `#[serdev(collect_errors)]` ) by default,
+    /// or the type given by `#[serde(validate(by = "...", error = "Type"))]`
+    type Error;
+
+    /// whether `Deserialize` of this type already runs `validate`
+    #[doc(hidden)]
+    const VALIDATED_IN_DESERIALIZE: bool = false;
+
+    fn validate(&self) -> Result<(), Self::Error>;
+
+    /// Apply `f` to a copy of the value, and commit the change only if it's still valid.
+    /// The value is left untouched when `validate` fails.
+    ///
+    /// ```
+    /// use serdev::Validate;
+    ///
+    /// #[derive(serdev::Deserialize, Clone)]
+    /// struct Stock {
+    ///     #[serdev(range(max = 100))]
+    ///     count: u32,
+    /// }
+    ///
+    /// let mut stock = Stock { count: 90 };
+    /// assert!(stock.modify(|s| s.count += 5).is_ok());
+    /// assert!(stock.modify(|s| s.count += 50).is_err());
+    /// assert_eq!(stock.count, 95);
+    /// ```
+    fn modify<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> Result<R, Self::Error>
+    where
+        Self: Clone,
+    {
+        let mut value = self.clone();
+        let r = f(&mut value);
+        value.validate()?;
+        *self = value;
+        Ok(r)
+    }
+}
@@ -0,0 +1,9 @@
+/// compile errors of misused attributes and rules, expected to point to the user's code
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+
+    #[cfg(feature = "regex")]
+    t.compile_fail("tests/ui/regex/*.rs");
+}
@@ -0,0 +1,9 @@
+use serdev::Deserialize;
+
+#[derive(Deserialize)]
+struct Code {
+    #[serdev(pattern = "^a$")]
+    value: u8,
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use serdev::Deserialize;
+
+struct OrderError;
+impl std::fmt::Display for OrderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("invalid order")
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(validate(by = "Self::validate", error = "OrderError"))]
+struct Order {
+    #[serdev(range(min = 1))]
+    quantity: u32,
+}
+impl Order {
+    fn validate(&self) -> Result<(), OrderError> {
+        Ok(())
+    }
+}
+
+fn main() {}
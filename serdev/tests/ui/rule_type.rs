@@ -0,0 +1,21 @@
+use serdev::Deserialize;
+
+#[derive(Deserialize)]
+struct Length {
+    #[serdev(length(max = 3))]
+    count: i32,
+}
+
+#[derive(Deserialize)]
+struct Range {
+    #[serdev(range(min = 1))]
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Format {
+    #[serdev(format = "email")]
+    mail: u8,
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+use serdev::Deserialize;
+
+#[derive(Deserialize)]
+struct Point {
+    #[serdev(lenght(max = 3))]
+    label: String,
+}
+
+#[derive(Deserialize)]
+struct Range {
+    #[serdev(range(mni = 0))]
+    start: i32,
+}
+
+#[derive(Deserialize)]
+#[serdev(validate_on = "desrialize")]
+struct Payment {
+    #[serdev(range(min = 1))]
+    amount: u64,
+}
+
+#[derive(Deserialize)]
+enum Quantity {
+    #[serdev(range(min = 1))]
+    Pieces(u8),
+    #[serdev(trim)]
+    Named(String),
+}
+
+fn main() {}
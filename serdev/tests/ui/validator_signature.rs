@@ -0,0 +1,25 @@
+use serdev::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(validate = "Self::validate")]
+struct Range {
+    start: i32,
+    end:   i32,
+}
+impl Range {
+    fn validate(&self) -> bool {
+        self.start <= self.end
+    }
+}
+
+#[derive(Deserialize)]
+struct User {
+    #[serde(validate = "not_blank")]
+    name: String,
+}
+fn not_blank(name: &i32) -> Result<(), String> {
+    if *name == 0 {return Err(String::from("blank"))}
+    Ok(())
+}
+
+fn main() {}
@@ -1,20 +1,37 @@
 mod target;
 mod validate;
 mod reexport;
+mod rules;
+mod name;
+mod collect;
+mod hook;
+mod validate_on;
+mod suggest;
+mod attr;
+mod keep;
 
 use self::target::Target;
 use self::validate::Validate;
 use self::reexport::Reexport;
+use self::rules::{OnFailure, VariantRules};
+use self::hook::Hook;
+use self::validate_on::ValidateOn;
+use self::keep::ProxyKeepAttrs;
 
 use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote, ToTokens};
-use syn::{Error, LitStr};
+use syn::{Error, GenericParam, Ident, LitStr};
 
 
+/// keys of `#[serdev(...)]` on a container
+const CONTAINER_KEYS: &[&str] = &["crate", "collect_errors", "validate_on", "proxy_keep_attrs"];
+
 pub(super) fn Serialize(input: TokenStream) -> Result<TokenStream, Error> {
     let mut target = syn::parse2::<Target>(input.clone())?;
 
-    let _ = Validate::take(target.attrs_mut())?;
+    let _ = Hook::take(target.attrs_mut(), "normalize")?;
+    let _ = Hook::take(target.attrs_mut(), "finalize")?;
+    let validates = has_validation(&Validate::take(target.attrs_mut())?, &target.take_rules()?);
 
     let (serdev, serde) = match Reexport::take(target.attrs_mut())? {
         None => (
@@ -27,11 +44,57 @@ pub(super) fn Serialize(input: TokenStream) -> Result<TokenStream, Error> {
         )
     };
 
+    let validate_on = ValidateOn::take(target.attrs_mut(), validates)?;
+    let _ = OnFailure::take(target.attrs_mut())?;
+    let _ = ProxyKeepAttrs::take(target.attrs_mut())?;
+    suggest::deny_unknown_serdev(target.attrs(), "serdev container attribute", CONTAINER_KEYS)?;
+
+    if !validate_on.on_serialize() {
+        return Ok(quote! {
+            #[derive(#serdev::__private__::serde::Serialize)]
+            #[serde(crate = #serde)]
+            #[#serdev::__private__::consume]
+            #target
+        })
+    }
+
+    /* serde generates inherent `serialize` for `remote = "Self"`, and the `Serialize` impl
+       calls it after validation. The inherent one is made private by the consumed target's visibility. */
+
+    let target_ident = target.ident().clone();
+    let generics = target.generics().clone();
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let where_clause = {
+        let mut generics = generics.clone();
+        let params = generics.type_params().map(|t| t.ident.clone()).collect::<Vec<_>>();
+        generics.make_where_clause().predicates.extend(params.into_iter().map(|t| -> syn::WherePredicate {
+            syn::parse_quote! {#t: #serdev::__private__::serde::Serialize}
+        }));
+        generics.where_clause
+    };
+
+    let remote = litstr(&target_ident.to_string());
+    *target.vis_mut() = syn::Visibility::Inherited;
+
     Ok(quote! {
-        #[derive(#serdev::__private__::serde::Serialize)]
-        #[serde(crate = #serde)]
-        #[#serdev::__private__::consume]
-        #target
+        const _: () = {
+            impl #impl_generics #serdev::__private__::serde::Serialize for #target_ident #ty_generics
+                #where_clause
+            {
+                fn serialize<S: #serdev::__private__::serde::Serializer>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error> {
+                    if let ::core::result::Result::Err(e) = <Self as #serdev::Validate>::validate(self) {
+                        return ::core::result::Result::Err(<S::Error as #serdev::__private__::serde::ser::Error>::custom(e))
+                    }
+                    Self::serialize(self, serializer)
+                }
+            }
+
+            #[derive(#serdev::__private__::serde::Serialize)]
+            #[serde(crate = #serde)]
+            #[serde(remote = #remote)]
+            #[#serdev::__private__::consume]
+            #target
+        };
     })
 }
 
@@ -52,67 +115,247 @@ pub(super) fn Deserialize(input: TokenStream) -> Result<TokenStream, Error> {
         )
     };
 
-    Ok(match Validate::take(target.attrs_mut())? {
-        Some(validate) => {
-            let proxy = target.create_proxy(format_ident!("serdev_proxy_{}", target.ident()));
+    let normalize  = Hook::take(target.attrs_mut(), "normalize")?;
+    let finalize   = Hook::take(target.attrs_mut(), "finalize")?;
+    let validate   = Validate::take(target.attrs_mut())?;
+    let rules      = target.take_rules()?;
+    let on_failure = OnFailure::take(target.attrs_mut())?;
+    let validate_on = ValidateOn::take(target.attrs_mut(), has_validation(&validate, &rules))?;
+    let keep       = ProxyKeepAttrs::take(target.attrs_mut())?;
+    suggest::deny_unknown_serdev(target.attrs(), "serdev container attribute", CONTAINER_KEYS)?;
 
-            let target_ident = target.ident();
-            let proxy_ident  = proxy.ident();
+    let validate_impl = validate_impl(&target, &serdev, validate, &rules, on_failure, validate_on.on_deserialize())?;
+    let validates = validate_impl.is_some() && validate_on.on_deserialize();
 
-            let transmute_from_proxy = proxy.transmute_expr("proxy", target_ident);
+    let normalize_this = {
+        let mut stmts = target.normalize_stmts(quote! {&mut this}, &rules)?;
+        if let Some(normalize) = normalize {
+            let normalize_fn = normalize.function()?;
+            stmts.extend(quote! {#normalize_fn(&mut this);})
+        }
+        stmts
+    };
 
-            let proxy_type_lit = litstr(&quote!(#proxy_ident #ty_generics).to_string());
+    let sanitizes = rules.iter().any(VariantRules::sanitizes);
 
-            let validate_fn = validate.function()?;
-            let (error_ty, e_as_error_ty) = match validate.error()? {
-                Some(ty) => (
-                    quote! {#ty},
-                    quote! {e}
-                ),
-                None => (
-                    quote! {#serdev::__private__::DefaultError},
-                    quote! {#serdev::__private__::default_error(e)}
-                )
-            };
+    let finalize_this = match finalize {
+        None           => quote! {},
+        Some(finalize) => {
+            let finalize_fn = finalize.function()?;
+            quote! {let this = #serdev::__private__::finalize(this, #finalize_fn);}
+        }
+    };
+
+    if !validates && !sanitizes && normalize_this.is_empty() && finalize_this.is_empty() && !target.tracks_path()? {
+        return Ok(quote! {
+            #validate_impl
+
+            #[derive(#serdev::__private__::serde::Deserialize)]
+            #[serde(crate = #serde)]
+            #[#serdev::__private__::consume]
+            #target
+        })
+    }
+
+    /* the proxy is named the same as the target in a nested scope, where the target is named `alias` */
+    let alias = format_ident!("serdev_target_{}", target.ident());
+    let alias_params = generics.params.iter().map(|param| match param {
+        GenericParam::Lifetime(l) => l.lifetime.to_token_stream(),
+        GenericParam::Type(t)     => t.ident.to_token_stream(),
+        GenericParam::Const(c)    => {let (ident, ty) = (&c.ident, &c.ty); quote! {const #ident: #ty}}
+    });
 
+    let (proxy, field_markers) = target.create_proxy(&alias, &keep, &serdev)?;
+
+    let target_ident = target.ident().clone();
+    let proxy_ident  = proxy.ident();
+
+    let transmute_from_proxy = proxy.transmute_expr("proxy", &Ident::new("Self", Span::call_site()), &rules, &serdev)?;
+
+    /* deserialized as `Fresh`, clearing the errors stashed by earlier calls before the proxy */
+    let proxy_type_lit = litstr(&quote!(#serdev::__private__::Fresh<#proxy_ident #ty_generics>).to_string());
+
+    let this = if normalize_this.is_empty() {
+        quote! {this}
+    } else {
+        quote! {mut this}
+    };
+
+    let (error_ty, try_from_body) = match validates {
+        true => (
+            quote! {<Self as #serdev::Validate>::Error},
             quote! {
-                const _: () = {
-                    #[derive(#serdev::__private__::serde::Deserialize)]
-                    #[serde(crate = #serde)]
-                    #[allow(non_camel_case_types)]
-                    #proxy
-
-                    impl #impl_generics ::core::convert::TryFrom<#proxy_ident #ty_generics> for #target_ident #ty_generics
-                        #where_clause
-                    {
-                        type Error = #error_ty;
-
-                        #[inline]
-                        fn try_from(proxy: #proxy_ident #ty_generics) -> ::core::result::Result<Self, Self::Error> {
-                            let this = #transmute_from_proxy;
-                            let _: () = #validate_fn(&this).map_err(|e| #e_as_error_ty)?;
-                            Ok(this)
+                let #this = #transmute_from_proxy;
+                #normalize_this
+                <Self as #serdev::Validate>::validate(&this)?;
+                #finalize_this
+                Ok(this)
+            }
+        ),
+        false => (
+            quote! {#serdev::__private__::DefaultError},
+            quote! {
+                let #this = #transmute_from_proxy;
+                #normalize_this
+                #finalize_this
+                Ok(this)
+            }
+        )
+    };
+
+    *target.ident_mut() = alias.clone();
+
+    Ok(quote! {
+        const _: () = {
+            #[allow(non_camel_case_types)]
+            type #alias<#(#alias_params),*> = #target_ident #ty_generics;
+
+            #field_markers
+
+            #validate_impl
+
+            const _: () = {
+                #[derive(#serdev::__private__::serde::Deserialize)]
+                #[serde(crate = #serde)]
+                #proxy
+
+                impl #impl_generics ::core::convert::TryFrom<#serdev::__private__::Fresh<#proxy_ident #ty_generics>> for #alias #ty_generics
+                    #where_clause
+                {
+                    type Error = #error_ty;
+
+                    #[inline]
+                    fn try_from(#serdev::__private__::Fresh(proxy): #serdev::__private__::Fresh<#proxy_ident #ty_generics>) -> ::core::result::Result<Self, Self::Error> {
+                        let result = (move || -> ::core::result::Result<Self, Self::Error> {
+                            #try_from_body
+                        })();
+                        if let ::core::result::Result::Err(e) = &result {
+                            use #serdev::__private__::{StashSerdevError as _, StashOther as _};
+                            (&#serdev::__private__::Stashing(e)).stash()
                         }
+                        result
                     }
+                }
 
-                    #[derive(#serdev::__private__::serde::Deserialize)]
-                    #[serde(crate = #serde)]
-                    #[serde(try_from = #proxy_type_lit)]
-                    #[#serdev::__private__::consume]
-                    #target
-                };
-            }
-        }
-
-        None => {
-            quote! {
                 #[derive(#serdev::__private__::serde::Deserialize)]
                 #[serde(crate = #serde)]
+                #[serde(try_from = #proxy_type_lit)]
                 #[#serdev::__private__::consume]
                 #target
+            };
+        };
+    })
+}
+
+pub(super) fn Validate(input: TokenStream) -> Result<TokenStream, Error> {
+    let mut target = syn::parse2::<Target>(input.clone())?;
+
+    let serdev = match Reexport::take(target.attrs_mut())? {
+        None    => quote! {::serdev},
+        Some(r) => r.path()?.into_token_stream()
+    };
+
+    let validate   = Validate::take(target.attrs_mut())?;
+    let rules      = target.take_rules()?;
+    let on_failure = OnFailure::take(target.attrs_mut())?;
+    let _ = ValidateOn::take(target.attrs_mut(), has_validation(&validate, &rules))?;
+    let _ = ProxyKeepAttrs::take(target.attrs_mut())?;
+    suggest::deny_unknown_serdev(target.attrs(), "serdev container attribute", CONTAINER_KEYS)?;
+
+    Ok(validate_impl(&target, &serdev, validate, &rules, on_failure, false)?.unwrap_or_else(|| {
+        let target_ident = target.ident();
+        let (impl_generics, ty_generics, where_clause) = target.generics().split_for_impl();
+        quote! {
+            impl #impl_generics #serdev::Validate for #target_ident #ty_generics
+                #where_clause
+            {
+                type Error = ::core::convert::Infallible;
+
+                #[inline]
+                fn validate(&self) -> ::core::result::Result<(), Self::Error> {
+                    Ok(())
+                }
             }
         }
-    })
+    }))
+}
+
+fn has_validation(validate: &[Validate], rules: &[VariantRules]) -> bool {
+    !validate.is_empty() || !rules.iter().all(VariantRules::is_empty)
+}
+
+/// `impl serdev::Validate` for `target` by the validation taken out of it,
+/// or `None` if `target` has no validation. `in_deserialize` tells it's run in `Deserialize`.
+fn validate_impl(
+    target:         &Target,
+    serdev:         &TokenStream,
+    validate:       Vec<Validate>,
+    rules:          &[VariantRules],
+    on_failure:     OnFailure,
+    in_deserialize: bool,
+) -> Result<Option<TokenStream>, Error> {
+    if !has_validation(&validate, rules) {
+        return Ok(None)
+    }
+
+    let target_ident = target.ident();
+    let (impl_generics, ty_generics, where_clause) = target.generics().split_for_impl();
+
+    let default_error_ty = match on_failure {
+        OnFailure::Return  => quote! {#serdev::__private__::DefaultError},
+        OnFailure::Collect => quote! {#serdev::__private__::DefaultErrors}
+    };
+
+    /* the first `error = "..."` is the error type, and the others are converted into it by `From` */
+    let custom_error_ty = validate.iter().find_map(|v| v.error().transpose()).transpose()?;
+
+    let checks = target.check_stmts(rules, serdev, on_failure, custom_error_ty.is_some())?;
+
+    let error_ty = match custom_error_ty {
+        Some(ty) => quote! {#ty},
+        None     => default_error_ty
+    };
+
+    let self_ty = quote!(#target_ident #ty_generics);
+    let validate_self = validate.iter().map(|validate| {
+        let error = validate.error()?;
+        Ok(validate.call(&[quote! {self}], std::slice::from_ref(&self_ty), matches!(on_failure, OnFailure::Collect))?
+            .into_iter()
+            .map(|validate_self| match error {
+                Some(_) => on_failure.handle(validate_self),
+                None    => on_failure.handle(quote! {#validate_self.map_err(#serdev::__private__::default_error)})
+            })
+            .collect::<TokenStream>())
+    }).collect::<Result<TokenStream, Error>>()?;
+
+    let body = match on_failure {
+        OnFailure::Return => quote! {
+            #checks
+            #validate_self
+            Ok(())
+        },
+        OnFailure::Collect => quote! {
+            let mut serdev_errors = <Self::Error as ::core::default::Default>::default();
+            let mut serdev_failed = false;
+            #checks
+            #validate_self
+            if serdev_failed {Err(serdev_errors)} else {Ok(())}
+        }
+    };
+
+    Ok(Some(quote! {
+        impl #impl_generics #serdev::Validate for #target_ident #ty_generics
+            #where_clause
+        {
+            type Error = #error_ty;
+
+            const VALIDATED_IN_DESERIALIZE: bool = #in_deserialize;
+
+            fn validate(&self) -> ::core::result::Result<(), Self::Error> {
+                #body
+            }
+        }
+    }))
 }
 
 fn litstr(value: &str) -> LitStr {
@@ -0,0 +1,78 @@
+use super::keep::ProxyKeepAttrs;
+use quote::ToTokens;
+use syn::{punctuated::Punctuated, spanned::Spanned, token, Attribute, Error, Expr, ExprLit, Lit, LitStr, MacroDelimiter, Meta, MetaList};
+
+
+/// directives in the `#[<namespace>(...)]`s of `attrs`, in the declared order,
+/// each parsed as a `Meta` keeping its original tokens
+pub(crate) fn directives(attrs: &[Attribute], namespace: &str) -> Result<Vec<Meta>, Error> {
+    let mut directives = Vec::new();
+    for attr in attrs {
+        if attr.path().is_ident(namespace) {
+            directives.extend(attr.parse_args_with(
+                Punctuated::<Meta, token::Comma>::parse_terminated
+            )?);
+        }
+    }; Ok(directives)
+}
+
+/// take the directives of `key` out of the `#[<namespace>(...)]`s of `attrs` in the declared order,
+/// leaving the other directives with their original tokens
+pub(crate) fn take(attrs: &mut [Attribute], namespace: &str, key: &str) -> Result<Vec<Meta>, Error> {
+    let mut taken = Vec::new();
+    for attr in attrs {
+        if attr.path().is_ident(namespace) {
+            let directives = attr.parse_args_with(
+                Punctuated::<Meta, token::Comma>::parse_terminated
+            )?;
+            if directives.iter().any(|d| d.path().is_ident(key)) {
+                let mut others = Punctuated::<Meta, token::Comma>::new();
+                for directive in directives {
+                    if directive.path().is_ident(key) {
+                        taken.push(directive)
+                    } else {
+                        others.push(directive)
+                    }
+                }
+                attr.meta = Meta::List(MetaList {
+                    path:      attr.path().clone(),
+                    delimiter: MacroDelimiter::Paren(token::Paren::default()),
+                    tokens:    others.into_token_stream()
+                });
+            }
+        }
+    }; Ok(taken)
+}
+
+/// `take` the directive of `key` that must appear at most once
+pub(crate) fn take_one(attrs: &mut [Attribute], namespace: &str, key: &str) -> Result<Option<Meta>, Error> {
+    let mut taken = take(attrs, namespace, key)?.into_iter();
+    match (taken.next(), taken.next()) {
+        (_, Some(duplicate)) => Err(Error::new(duplicate.span(), format!("duplicate `{key}`"))),
+        (directive, None)    => Ok(directive)
+    }
+}
+
+/// `"..."` of `key = "..."`
+pub(crate) fn litstr_value(directive: &Meta) -> Result<&LitStr, Error> {
+    match directive {
+        Meta::NameValue(nv) => litstr(&nv.value)
+            .ok_or_else(|| Error::new(nv.value.span(), "expected a string literal")),
+        _ => Err(Error::new(directive.span(), format!(
+            "expected `{} = \"...\"`", directive.path().to_token_stream()
+        )))
+    }
+}
+
+pub(crate) fn litstr(expr: &Expr) -> Option<&LitStr> {
+    match expr {
+        Expr::Lit(ExprLit { lit: Lit::Str(s), .. }) => Some(s),
+        _ => None
+    }
+}
+
+/// `attr` as put on the proxy, or `None` if the proxy doesn't have it:
+/// `#[serde(...)]` and the ones in `keep`
+pub(crate) fn for_proxy(attr: &Attribute, keep: &ProxyKeepAttrs) -> Option<Attribute> {
+    (attr.path().is_ident("serde") || keep.keeps(attr.path())).then(|| attr.clone())
+}
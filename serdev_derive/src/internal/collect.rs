@@ -0,0 +1,16 @@
+use super::attr;
+use syn::{spanned::Spanned, Attribute, Error, Meta};
+
+
+/// `#[serdev(collect_errors)]`
+pub(crate) struct CollectErrors;
+
+impl CollectErrors {
+    pub(crate) fn take(attrs: &mut [Attribute]) -> Result<Option<Self>, Error> {
+        match attr::take_one(attrs, "serdev", "collect_errors")? {
+            None                => Ok(None),
+            Some(Meta::Path(_)) => Ok(Some(Self)),
+            Some(other)         => Err(Error::new(other.span(), "expected `collect_errors` without any value"))
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use super::attr;
+use syn::{Attribute, Error, LitStr, Path};
+
+
+/// `#[serde(normalize = "function")]` or `#[serde(finalize = "function")]` on a container
+pub(crate) struct Hook {
+    by: LitStr,
+}
+
+impl Hook {
+    /// take `#[serde(<key> = "function")]` out of `attrs`
+    pub(crate) fn take(attrs: &mut [Attribute], key: &str) -> Result<Option<Self>, Error> {
+        attr::take_one(attrs, "serde", key)?
+            .map(|directive| Ok(Self { by: attr::litstr_value(&directive)?.clone() }))
+            .transpose()
+    }
+
+    pub(crate) fn function(&self) -> Result<Path, Error> {
+        self.by.parse()
+    }
+}
@@ -0,0 +1,31 @@
+use super::attr;
+use quote::ToTokens;
+use syn::{punctuated::Punctuated, spanned::Spanned, token, Attribute, Error, Path};
+
+
+/// `#[serdev(proxy_keep_attrs(path, ...))]`, the attributes put on the proxy
+/// in addition to serde's ones ( see `attr::for_proxy` )
+#[derive(Default)]
+pub(crate) struct ProxyKeepAttrs {
+    /// the paths as tokens printed, as `syn::Path`s can't be compared without `extra-traits`
+    paths: Vec<String>,
+}
+
+impl ProxyKeepAttrs {
+    pub(crate) fn take(attrs: &mut [Attribute]) -> Result<Self, Error> {
+        let Some(directive) = attr::take_one(attrs, "serdev", "proxy_keep_attrs")? else {
+            return Ok(Self::default())
+        };
+        let paths = directive.require_list()
+            .map_err(|_| Error::new(directive.span(), "expected `proxy_keep_attrs(attr, ...)`"))?
+            .parse_args_with(Punctuated::<Path, token::Comma>::parse_terminated)?;
+        if let Some(serdev) = paths.iter().find(|p| p.is_ident("serdev")) {
+            return Err(Error::new(serdev.span(), "`serdev` attributes can't be kept on the proxy"))
+        }
+        Ok(Self { paths: paths.iter().map(|p| p.to_token_stream().to_string()).collect() })
+    }
+
+    pub(crate) fn keeps(&self, path: &Path) -> bool {
+        self.paths.contains(&path.to_token_stream().to_string())
+    }
+}
@@ -0,0 +1,186 @@
+use super::{attr, suggest};
+use syn::{punctuated::Punctuated, token, Attribute, Error, Field, Ident, LitStr, Meta, Variant};
+
+
+/// serde's `rename_all` rules, used to report fields by the name they have on the wire
+#[derive(Clone, Copy)]
+pub(crate) enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn from_str(rule: &str) -> Option<Self> {
+        match rule {
+            "lowercase"            => Some(Self::Lower),
+            "UPPERCASE"            => Some(Self::Upper),
+            "PascalCase"           => Some(Self::Pascal),
+            "camelCase"            => Some(Self::Camel),
+            "snake_case"           => Some(Self::Snake),
+            "SCREAMING_SNAKE_CASE" => Some(Self::ScreamingSnake),
+            "kebab-case"           => Some(Self::Kebab),
+            "SCREAMING-KEBAB-CASE" => Some(Self::ScreamingKebab),
+            _ => None
+        }
+    }
+
+    /// apply to a variant name, which is expected to be in PascalCase
+    pub(crate) fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            Self::Pascal => variant.to_owned(),
+            Self::Lower  => variant.to_ascii_lowercase(),
+            Self::Upper  => variant.to_ascii_uppercase(),
+            Self::Camel  => {
+                let mut chars = variant.chars();
+                match chars.next() {
+                    None        => String::new(),
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str()
+                }
+            }
+            Self::Snake  => {
+                let mut snake = String::with_capacity(variant.len());
+                for (i, c) in variant.char_indices() {
+                    if i > 0 && c.is_uppercase() {
+                        snake.push('_')
+                    }
+                    snake.push(c.to_ascii_lowercase())
+                }
+                snake
+            }
+            Self::ScreamingSnake => Self::Snake.apply_to_variant(variant).to_ascii_uppercase(),
+            Self::Kebab          => Self::Snake.apply_to_variant(variant).replace('_', "-"),
+            Self::ScreamingKebab => Self::ScreamingSnake.apply_to_variant(variant).replace('_', "-"),
+        }
+    }
+
+    /// apply to a field name, which is expected to be in snake_case
+    pub(crate) fn apply_to_field(self, field: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => field.to_owned(),
+            Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+            Self::Kebab => field.replace('_', "-"),
+            Self::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+            Self::Pascal | Self::Camel => {
+                let mut pascal = String::with_capacity(field.len());
+                let mut capitalize = true;
+                for c in field.chars() {
+                    if c == '_' {
+                        capitalize = true
+                    } else if capitalize {
+                        pascal.push(c.to_ascii_uppercase());
+                        capitalize = false
+                    } else {
+                        pascal.push(c)
+                    }
+                }
+                if matches!(self, Self::Camel) {
+                    if let Some(first) = pascal.get_mut(0..1) {
+                        first.make_ascii_lowercase()
+                    }
+                }
+                pascal
+            }
+        }
+    }
+}
+
+/// `rename_all` (or the given `key`, like `rename_all_fields`) in `#[serde(...)]`s,
+/// respecting the `deserialize` side of `rename_all(serialize = "...", deserialize = "...")`
+pub(crate) fn rename_all(attrs: &[Attribute], key: &str) -> Result<Option<RenameRule>, Error> {
+    deserialize_name(attrs, key)?
+        .map(|rule| RenameRule::from_str(&rule.value())
+            .ok_or_else(|| suggest::unknown(rule.span(), "rename rule", &rule.value(), &[
+                "lowercase", "UPPERCASE", "PascalCase", "camelCase", "snake_case",
+                "SCREAMING_SNAKE_CASE", "kebab-case", "SCREAMING-KEBAB-CASE",
+            ]))
+        )
+        .transpose()
+}
+
+/// the name of `field` on the wire, following `#[serde(rename = "...")]` or `rename_all`
+pub(crate) fn field_name(field: &Field, index: usize, rename_all: Option<RenameRule>) -> Result<String, Error> {
+    if let Some(rename) = deserialize_name(&field.attrs, "rename")? {
+        return Ok(rename.value())
+    }
+    Ok(match &field.ident {
+        None => index.to_string(),
+        Some(ident) => {
+            let ident = ident_str(ident);
+            match rename_all {
+                None       => ident,
+                Some(rule) => rule.apply_to_field(&ident)
+            }
+        }
+    })
+}
+
+/// the name of `variant` on the wire, following `#[serde(rename = "...")]` or `rename_all`
+pub(crate) fn variant_name(variant: &Variant, rename_all: Option<RenameRule>) -> Result<String, Error> {
+    if let Some(rename) = deserialize_name(&variant.attrs, "rename")? {
+        return Ok(rename.value())
+    }
+    let ident = ident_str(&variant.ident);
+    Ok(match rename_all {
+        None       => ident,
+        Some(rule) => rule.apply_to_variant(&ident)
+    })
+}
+
+/// the path segment of `variant` for the errors in it, as the key its fields are under on the wire:
+/// the variant's name when externally tagged, the `content` key when adjacently tagged, or `None`
+/// when internally tagged by `tag` alone or untagged by `#[serde(untagged)]` on it or on the enum of `enum_attrs`
+pub(crate) fn variant_segment(enum_attrs: &[Attribute], variant: &Variant, rename_all: Option<RenameRule>) -> Result<Option<String>, Error> {
+    for attrs in [enum_attrs, &variant.attrs] {
+        if attr::directives(attrs, "serde")?.iter().any(|d| d.path().is_ident("untagged")) {
+            return Ok(None)
+        }
+    }
+    let key = |key: &str| -> Result<Option<String>, Error> {
+        Ok(attr::directives(enum_attrs, "serde")?.iter()
+            .find(|d| d.path().is_ident(key))
+            .map(attr::litstr_value).transpose()?
+            .map(LitStr::value))
+    };
+    match (key("tag")?, key("content")?) {
+        (Some(_), Some(content)) => Ok(Some(content)),
+        (Some(_), None)          => Ok(None),
+        (None, _)                => variant_name(variant, rename_all).map(Some)
+    }
+}
+
+fn ident_str(ident: &Ident) -> String {
+    let ident = ident.to_string();
+    match ident.strip_prefix("r#") {
+        Some(raw) => raw.to_owned(),
+        None      => ident
+    }
+}
+
+fn deserialize_name(attrs: &[Attribute], key: &str) -> Result<Option<LitStr>, Error> {
+    for directive in attr::directives(attrs, "serde")? {
+        match directive {
+            Meta::NameValue(nv) if nv.path.is_ident(key) => {
+                return Ok(attr::litstr(&nv.value).cloned())
+            }
+            Meta::List(list) if list.path.is_ident(key) => {
+                let sides = list.parse_args_with(
+                    Punctuated::<Meta, token::Comma>::parse_terminated
+                )?;
+                for side in sides {
+                    if let Meta::NameValue(nv) = side {
+                        if nv.path.is_ident("deserialize") {
+                            return Ok(attr::litstr(&nv.value).cloned())
+                        }
+                    }
+                }
+            }
+            _ => ()
+        }
+    }; Ok(None)
+}
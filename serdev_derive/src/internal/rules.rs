@@ -0,0 +1,283 @@
+mod range;
+mod length;
+mod pattern;
+mod format;
+mod sanitize;
+
+use self::range::Range;
+use self::length::Length;
+use self::pattern::Pattern;
+use self::format::Format;
+use self::sanitize::Sanitize;
+use super::validate::{respan, Validate};
+use super::collect::CollectErrors;
+use super::{attr, suggest};
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{meta::ParseNestedMeta, spanned::Spanned, Attribute, Error, Field, Ident, LitStr, Path, Variant};
+
+
+/// built-in validation rule put on a field by `#[serdev(...)]`
+trait Rule {
+    /// parse the directive at `meta` if it's one of this rule's, returning whether it was
+    fn parse(&mut self, meta: &ParseNestedMeta) -> Result<bool, Error>;
+
+    /// whether no directive of this rule has been parsed
+    fn is_empty(&self) -> bool;
+
+    /// expressions of type `Result<(), serdev::Error>` checking `value`, a reference to the field,
+    /// spanned on the directives
+    fn checks(&self, value: &TokenStream, serdev: &TokenStream) -> Vec<TokenStream>;
+}
+
+/// how a failed check is handled in the generated `try_from`
+#[derive(Clone, Copy)]
+pub(crate) enum OnFailure {
+    /// return the error by `?`, converting it into the `try_from`'s error type by `From`
+    Return,
+    /// add the error to `serdev_errors: impl Extend<E>` and set `serdev_failed`,
+    /// for `#[serdev(collect_errors)]`
+    Collect,
+}
+
+impl OnFailure {
+    /// `Collect` if `#[serdev(collect_errors)]` is taken out of the container's `attrs`
+    pub(crate) fn take(attrs: &mut [Attribute]) -> Result<Self, Error> {
+        Ok(match CollectErrors::take(attrs)? {
+            Some(CollectErrors) => Self::Collect,
+            None                => Self::Return
+        })
+    }
+
+    /// statement handling the failure of `check`, an expression of type `Result<(), E>`
+    pub(crate) fn handle(self, check: TokenStream) -> TokenStream {
+        match self {
+            Self::Return => quote! {
+                let _: () = #check?;
+            },
+            Self::Collect => quote! {
+                if let ::core::result::Result::Err(e) = #check {
+                    ::core::iter::Extend::extend(&mut serdev_errors, ::core::iter::once(e));
+                    serdev_failed = true;
+                }
+            }
+        }
+    }
+}
+
+/// validation rules put on a field, and its sanitizers and normalizer run before them
+pub(crate) struct FieldRules {
+    name:      String,
+    /// path segment of the variant this field is in, if any ( see `name::variant_segment` )
+    variant:   Option<String>,
+    /// the field's type, for diagnostics
+    ty:        TokenStream,
+    sanitize:  Sanitize,
+    normalize: Option<Path>,
+    validate:  Vec<Validate>,
+    range:     Range,
+    length:   Length,
+    pattern:  Pattern,
+    format:   Format,
+}
+
+/// validation rules put on an enum variant and its fields.
+/// A struct is treated as a single variant having no rules of its own.
+pub(crate) struct VariantRules {
+    /// path segment of the variant, if any ( see `name::variant_segment` )
+    segment:  Option<String>,
+    validate: Vec<Validate>,
+    fields:   Vec<FieldRules>,
+}
+
+impl FieldRules {
+    /// take the rules out of `field`'s attributes, leaving only the ones serde knows
+    pub(crate) fn take(field: &mut Field, name: String, variant: Option<String>) -> Result<Self, Error> {
+        let validate = Validate::take(&mut field.attrs)?;
+
+        let mut sanitize  = Sanitize::default();
+        let mut normalize = None;
+        let mut range     = Range::default();
+        let mut length    = Length::default();
+        let mut pattern   = Pattern::default();
+        let mut format    = Format::default();
+        for attr in &field.attrs {
+            if attr.path().is_ident("serdev") {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("normalize") {
+                        normalize = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                        Ok(())
+                    } else if sanitize.parse(&meta)?
+                    || range.parse(&meta)?
+                    || length.parse(&meta)?
+                    || pattern.parse(&meta)?
+                    || format.parse(&meta)? {
+                        Ok(())
+                    } else {
+                        Err(suggest::unknown_meta(&meta, "serdev field attribute", &[
+                            "normalize", "trim", "lowercase", "uppercase", "nfc", "collapse_whitespace",
+                            "range", "multiple_of", "length", "pattern", "format",
+                        ]))
+                    }
+                })?;
+            }
+        }
+        field.attrs.retain(|a| !a.path().is_ident("serdev"));
+
+        Ok(Self { name, variant, ty: field.ty.to_token_stream(), sanitize, normalize, validate, range, length, pattern, format })
+    }
+
+    /// whether this has no checks, regardless of the sanitizers and the normalizer
+    pub(crate) fn is_empty(&self) -> bool {
+        self.validate.is_empty() &&
+        self.range.is_empty() &&
+        self.length.is_empty() &&
+        self.pattern.is_empty() &&
+        self.format.is_empty()
+    }
+
+    /// statements sanitizing the field value bound to `binding` as mutable
+    fn sanitize(&self, binding: &Ident, serdev: &TokenStream) -> TokenStream {
+        self.sanitize.stmts(&quote! {&mut #binding}, serdev)
+    }
+
+    /// statement normalizing the field by the mutable reference bound to `binding`
+    fn normalize(&self, binding: &Ident) -> TokenStream {
+        match &self.normalize {
+            None            => quote! {},
+            Some(normalize) => quote! {#normalize(#binding);}
+        }
+    }
+
+    /// statements checking the reference to the field value bound to `binding`, handling failures by `on_failure`
+    /// ( see `VariantRules::checks` for `custom_error` )
+    fn checks(&self, binding: &Ident, serdev: &TokenStream, on_failure: OnFailure, custom_error: bool) -> Result<TokenStream, Error> {
+        let name = &self.name;
+        let in_variant = match &self.variant {
+            None          => quote! {},
+            Some(variant) => quote! {.map_err(|e| #serdev::__private__::in_variant(#variant, e))}
+        };
+
+        let value = quote! {#binding};
+
+        let builtins = [
+            self.range.checks(&value, serdev),
+            self.length.checks(&value, serdev),
+            self.pattern.checks(&value, serdev),
+            self.format.checks(&value, serdev),
+        ];
+
+        let mut checks = builtins.into_iter().flatten()
+            .map(|check| {
+                /* all spanned as the check including the `?`, or type errors in it and failures of
+                   converting its error into the container's one are reported at the derive */
+                let span = check.clone().into_iter().next().map_or_else(Span::call_site, |t| t.span());
+                respan(on_failure.handle(quote! {
+                    #check.map_err(|e| #serdev::__private__::rule_error(#name, e)) #in_variant
+                }), span)
+            })
+            .collect::<TokenStream>();
+
+        for validate in &self.validate {
+            let error = validate.error()?;
+            for validate_field in validate.call(&[quote! {#binding}], std::slice::from_ref(&self.ty), false)? {
+                checks.extend(on_failure.handle(match error {
+                    Some(_) if custom_error => quote! {
+                        #validate_field
+                    },
+                    _ => quote! {
+                        #validate_field.map_err(|e| #serdev::__private__::field_error(#name, e)) #in_variant
+                    }
+                }))
+            }
+        }
+
+        Ok(checks)
+    }
+}
+
+impl VariantRules {
+    /// rules of a struct, whose own rules are handled as the container's ones
+    pub(crate) fn of_struct(fields: Vec<FieldRules>) -> Self {
+        Self { segment: None, validate: Vec::new(), fields }
+    }
+
+    /// take the rules out of `variant`'s attributes, leaving only the ones serde knows
+    pub(crate) fn take(variant: &mut Variant, segment: Option<String>, fields: Vec<FieldRules>) -> Result<Self, Error> {
+        let validate = Validate::take(&mut variant.attrs)?;
+        if let Some(directive) = attr::directives(&variant.attrs, "serdev")?.first() {
+            return Err(Error::new(directive.path().span(), format!(
+                "`#[serdev(...)]` is not supported on enum variants, put `{}` on the variant's fields instead",
+                directive.path().to_token_stream()
+            )))
+        }
+        Ok(Self { segment, validate, fields })
+    }
+
+    /// whether this has no checks, regardless of the sanitizers and the normalizers
+    pub(crate) fn is_empty(&self) -> bool {
+        self.validate.is_empty() && self.fields.iter().all(FieldRules::is_empty)
+    }
+
+    pub(crate) fn sanitizes(&self) -> bool {
+        self.fields.iter().any(|f| !f.sanitize.is_empty())
+    }
+
+    /// whether the field at `index` has some sanitizers
+    pub(crate) fn sanitizes_field(&self, index: usize) -> bool {
+        self.fields.get(index).is_some_and(|f| !f.sanitize.is_empty())
+    }
+
+    /// statements sanitizing the field values bound to `bindings` as mutable
+    pub(crate) fn sanitize_stmts(&self, bindings: &[Ident], serdev: &TokenStream) -> TokenStream {
+        bindings.iter().zip(&self.fields)
+            .map(|(binding, rules)| rules.sanitize(binding, serdev))
+            .collect()
+    }
+
+    pub(crate) fn normalizes(&self) -> bool {
+        self.fields.iter().any(|f| f.normalize.is_some())
+    }
+
+    /// statements normalizing the fields by the mutable references bound to `bindings`
+    pub(crate) fn normalize_stmts(&self, bindings: &[Ident]) -> TokenStream {
+        bindings.iter().zip(&self.fields)
+            .map(|(binding, rules)| rules.normalize(binding))
+            .collect()
+    }
+
+    /// statements checking the references to the fields bound to `bindings` one by one,
+    /// and then the variant's own rules taking all of them,
+    /// handling failures by `on_failure`.
+    ///
+    /// Errors of validators with `error = "..."` are passed through when `custom_error`, the container's error
+    /// type is also given by `error = "..."`, or otherwise `Display`ed as `serdev::Error`s with the path like the others.
+    pub(crate) fn checks(&self, bindings: &[Ident], serdev: &TokenStream, on_failure: OnFailure, custom_error: bool) -> Result<TokenStream, Error> {
+        let mut checks = bindings.iter().zip(&self.fields)
+            .map(|(binding, rules)| rules.checks(binding, serdev, on_failure, custom_error))
+            .collect::<Result<TokenStream, Error>>()?;
+
+        for validate in &self.validate {
+            let error = validate.error()?;
+            for validate_variant in validate.call(
+                &bindings.iter().map(|b| quote! {#b}).collect::<Vec<_>>(),
+                &self.fields.iter().map(|f| f.ty.clone()).collect::<Vec<_>>(),
+                false
+            )? {
+                checks.extend(on_failure.handle(match (&error, &self.segment) {
+                    (Some(_), _) if custom_error => quote! {
+                        #validate_variant
+                    },
+                    (_, Some(segment)) => quote! {
+                        #validate_variant.map_err(|e| #serdev::__private__::field_error(#segment, e))
+                    },
+                    (_, None) => quote! {
+                        #validate_variant.map_err(#serdev::__private__::default_error)
+                    }
+                }))
+            }
+        }
+
+        Ok(checks)
+    }
+}
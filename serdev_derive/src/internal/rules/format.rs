@@ -0,0 +1,43 @@
+use super::super::{suggest, validate::respan};
+use super::Rule;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote_spanned};
+use syn::{meta::ParseNestedMeta, Error, LitStr};
+
+
+/// `#[serdev(format = "..")]`
+#[derive(Default)]
+pub(crate) struct Format {
+    format: Option<LitStr>,
+}
+
+const FORMATS: &[&str] = &["email", "url", "uuid", "ipv4", "ipv6", "hostname"];
+
+impl Rule for Format {
+    fn parse(&mut self, meta: &ParseNestedMeta) -> Result<bool, Error> {
+        if !meta.path.is_ident("format") {
+            return Ok(false)
+        }
+
+        let format: LitStr = meta.value()?.parse()?;
+        if !FORMATS.contains(&&*format.value()) {
+            return Err(suggest::unknown(format.span(), "format", &format.value(), FORMATS))
+        }
+        self.format = Some(format);
+        Ok(true)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.format.is_none()
+    }
+
+    fn checks(&self, value: &TokenStream, serdev: &TokenStream) -> Vec<TokenStream> {
+        self.format.iter().map(|format| {
+            let check = format_ident!("format_{}", format.value());
+            let serdev = respan(serdev.clone(), format.span());
+            quote_spanned! {format.span()=>
+                #serdev::__private__::#check(#value)
+            }
+        }).collect()
+    }
+}
@@ -0,0 +1,88 @@
+use super::super::{suggest, validate::respan};
+use super::Rule;
+use proc_macro2::TokenStream;
+use quote::quote_spanned;
+use syn::{spanned::Spanned, meta::ParseNestedMeta, Error, Expr, LitStr};
+
+
+/// `#[serdev(length(min = .., max = .., equal = .., unit = ".."))]`
+#[derive(Default)]
+pub(crate) struct Length {
+    min:   Option<Expr>,
+    max:   Option<Expr>,
+    equal: Option<Expr>,
+    unit:  Option<Unit>,
+}
+
+enum Unit {
+    Bytes,
+    Chars,
+    Graphemes,
+}
+
+impl Rule for Length {
+    fn parse(&mut self, meta: &ParseNestedMeta) -> Result<bool, Error> {
+        if !meta.path.is_ident("length") {
+            return Ok(false)
+        }
+
+        meta.parse_nested_meta(|meta| {
+            if meta.path.is_ident("unit") {
+                let unit: LitStr = meta.value()?.parse()?;
+                self.unit = Some(match &*unit.value() {
+                    "bytes"     => Unit::Bytes,
+                    "chars"     => Unit::Chars,
+                    "graphemes" => Unit::Graphemes,
+                    other => return Err(suggest::unknown(unit.span(), "length unit", other, &["bytes", "chars", "graphemes"]))
+                });
+                return Ok(())
+            }
+
+            let bound = if meta.path.is_ident("min") {
+                &mut self.min
+            } else if meta.path.is_ident("max") {
+                &mut self.max
+            } else if meta.path.is_ident("equal") {
+                &mut self.equal
+            } else {
+                return Err(suggest::unknown_meta(&meta, "length bound", &["min", "max", "equal", "unit"]))
+            };
+            *bound = Some(meta.value()?.parse()?);
+            Ok(())
+        })?;
+
+        if self.equal.is_some() && (self.min.is_some() || self.max.is_some()) {
+            return Err(meta.error("`equal` can't be used together with `min` or `max`"))
+        }
+        if self.min.is_none() && self.max.is_none() && self.equal.is_none() {
+            return Err(meta.error("expected at least one of `min`, `max` or `equal`"))
+        }
+        Ok(true)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.min.is_none() &&
+        self.max.is_none() &&
+        self.equal.is_none()
+    }
+
+    fn checks(&self, value: &TokenStream, serdev: &TokenStream) -> Vec<TokenStream> {
+        [
+            ("min",   &self.min),
+            ("max",   &self.max),
+            ("equal", &self.equal),
+        ].into_iter().filter_map(|(rule, bound)| bound.as_ref().map(|bound| {
+            let serdev = respan(serdev.clone(), bound.span());
+            let check = quote::format_ident!("length_{rule}");
+            let measure = match self.unit {
+                None                  => quote_spanned! {bound.span()=> #serdev::__private__::Length::length},
+                Some(Unit::Bytes)     => quote_spanned! {bound.span()=> #serdev::__private__::TextLength::bytes},
+                Some(Unit::Chars)     => quote_spanned! {bound.span()=> #serdev::__private__::TextLength::chars},
+                Some(Unit::Graphemes) => quote_spanned! {bound.span()=> #serdev::__private__::TextLength::graphemes},
+            };
+            quote_spanned! {bound.span()=>
+                #serdev::__private__::#check(#value, #measure, #bound)
+            }
+        })).collect()
+    }
+}
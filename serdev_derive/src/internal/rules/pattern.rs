@@ -0,0 +1,50 @@
+use super::super::validate::respan;
+use super::Rule;
+use proc_macro2::TokenStream;
+use quote::quote_spanned;
+use syn::{meta::ParseNestedMeta, Error, LitStr};
+
+
+/// `#[serdev(pattern = "..")]`, requiring `regex` feature
+#[derive(Default)]
+pub(crate) struct Pattern {
+    pattern: Option<LitStr>,
+}
+
+impl Rule for Pattern {
+    fn parse(&mut self, meta: &ParseNestedMeta) -> Result<bool, Error> {
+        if !meta.path.is_ident("pattern") {
+            return Ok(false)
+        }
+
+        let pattern: LitStr = meta.value()?.parse()?;
+
+        #[cfg(not(feature = "regex"))] {
+            Err(Error::new(pattern.span(), "`pattern` requires `regex` feature of serdev"))
+        }
+
+        #[cfg(feature = "regex")] {
+            if let Err(e) = regex_syntax::Parser::new().parse(&pattern.value()) {
+                return Err(Error::new(pattern.span(), format!("invalid pattern: {e}")))
+            }
+            self.pattern = Some(pattern);
+            Ok(true)
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pattern.is_none()
+    }
+
+    fn checks(&self, value: &TokenStream, serdev: &TokenStream) -> Vec<TokenStream> {
+        self.pattern.iter().map(|pattern| {
+            let serdev = respan(serdev.clone(), pattern.span());
+            quote_spanned! {pattern.span()=>
+                #serdev::__private__::pattern_match(#value, {
+                    static PATTERN: #serdev::__private__::Pattern = #serdev::__private__::Pattern::new(#pattern);
+                    &PATTERN
+                })
+            }
+        }).collect()
+    }
+}
@@ -0,0 +1,90 @@
+use super::super::{suggest, validate::respan};
+use super::Rule;
+use proc_macro2::TokenStream;
+use quote::quote_spanned;
+use syn::{spanned::Spanned, meta::ParseNestedMeta, Error, Expr, ExprLit, ExprUnary, Lit, UnOp};
+
+
+/// `#[serdev(range(min = .., max = .., exclusive_min = .., exclusive_max = ..), multiple_of = ..)]`
+#[derive(Default)]
+pub(crate) struct Range {
+    min:           Option<Expr>,
+    max:           Option<Expr>,
+    exclusive_min: Option<Expr>,
+    exclusive_max: Option<Expr>,
+    multiple_of:   Option<Expr>,
+}
+
+impl Rule for Range {
+    fn parse(&mut self, meta: &ParseNestedMeta) -> Result<bool, Error> {
+        if meta.path.is_ident("range") {
+            meta.parse_nested_meta(|meta| {
+                let bound = if meta.path.is_ident("min") {
+                    &mut self.min
+                } else if meta.path.is_ident("max") {
+                    &mut self.max
+                } else if meta.path.is_ident("exclusive_min") {
+                    &mut self.exclusive_min
+                } else if meta.path.is_ident("exclusive_max") {
+                    &mut self.exclusive_max
+                } else {
+                    return Err(suggest::unknown_meta(&meta, "range bound", &["min", "max", "exclusive_min", "exclusive_max"]))
+                };
+                *bound = Some(meta.value()?.parse()?);
+                Ok(())
+            })?;
+            if self.min.is_some() && self.exclusive_min.is_some() {
+                return Err(meta.error("`min` and `exclusive_min` can't be used together"))
+            }
+            if self.max.is_some() && self.exclusive_max.is_some() {
+                return Err(meta.error("`max` and `exclusive_max` can't be used together"))
+            }
+            Ok(true)
+
+        } else if meta.path.is_ident("multiple_of") {
+            let multiple_of: Expr = meta.value()?.parse()?;
+            match &multiple_of {
+                Expr::Lit(ExprLit { lit, .. }) if match lit {
+                    Lit::Int(i)   => i.base10_digits().bytes().all(|b| b == b'0'),
+                    Lit::Float(f) => f.base10_digits().parse::<f64>().is_ok_and(|f| f == 0.),
+                    _ => false
+                } => {
+                    return Err(Error::new_spanned(lit, "`multiple_of` must not be zero"))
+                }
+                Expr::Unary(ExprUnary { op: UnOp::Neg(_), .. }) => {
+                    return Err(Error::new_spanned(&multiple_of, "`multiple_of` must be positive"))
+                }
+                _ => ()
+            }
+            self.multiple_of = Some(multiple_of);
+            Ok(true)
+
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.min.is_none() &&
+        self.max.is_none() &&
+        self.exclusive_min.is_none() &&
+        self.exclusive_max.is_none() &&
+        self.multiple_of.is_none()
+    }
+
+    fn checks(&self, value: &TokenStream, serdev: &TokenStream) -> Vec<TokenStream> {
+        [
+            ("min",           &self.min),
+            ("max",           &self.max),
+            ("exclusive_min", &self.exclusive_min),
+            ("exclusive_max", &self.exclusive_max),
+            ("multiple_of",   &self.multiple_of),
+        ].into_iter().filter_map(|(rule, bound)| bound.as_ref().map(|bound| {
+            let serdev = respan(serdev.clone(), bound.span());
+            let check = quote::format_ident!("number_{rule}");
+            quote_spanned! {bound.span()=>
+                #serdev::__private__::#check(#value, #bound)
+            }
+        })).collect()
+    }
+}
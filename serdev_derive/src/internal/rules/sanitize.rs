@@ -0,0 +1,45 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{meta::ParseNestedMeta, Error, Ident};
+
+
+/// `#[serdev(trim, lowercase, uppercase, nfc, collapse_whitespace)]`, applied in declaration order.
+/// `nfc` requires `unicode` feature.
+#[derive(Default)]
+pub(crate) struct Sanitize {
+    sanitizers: Vec<Ident>,
+}
+
+impl Sanitize {
+    /// push the sanitizer named by `meta` if it's one, returning whether it was
+    pub(crate) fn parse(&mut self, meta: &ParseNestedMeta) -> Result<bool, Error> {
+        let Some(name) = meta.path.get_ident() else {
+            return Ok(false)
+        };
+        if !["trim", "lowercase", "uppercase", "nfc", "collapse_whitespace"].contains(&&*name.to_string()) {
+            return Ok(false)
+        }
+
+        #[cfg(not(feature = "unicode"))]
+        if name == "nfc" {
+            return Err(Error::new(name.span(), "`nfc` requires `unicode` feature of serdev"))
+        }
+
+        self.sanitizers.push(name.clone());
+        Ok(true)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.sanitizers.is_empty()
+    }
+
+    /// statements sanitizing `value`, a mutable reference to the field
+    pub(crate) fn stmts(&self, value: &TokenStream, serdev: &TokenStream) -> TokenStream {
+        self.sanitizers.iter().map(|name| {
+            let sanitize = format_ident!("sanitize_{name}");
+            quote! {
+                #serdev::__private__::#sanitize(#value);
+            }
+        }).collect()
+    }
+}
@@ -0,0 +1,62 @@
+use super::attr;
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::{meta::ParseNestedMeta, spanned::Spanned, Attribute, Error};
+
+
+/// error for the `unknown` name of `what`, suggesting the most similar one of `candidates` if any
+/// like "unknown validate key `eror`, did you mean `error`?"
+pub(crate) fn unknown(span: Span, what: &str, unknown: &str, candidates: &[&str]) -> Error {
+    match similar(unknown, candidates) {
+        Some(candidate) => Error::new(span, format!("unknown {what} `{unknown}`, did you mean `{candidate}`?")),
+        None => Error::new(span, format!(
+            "unknown {what} `{unknown}`, expected one of {}",
+            candidates.iter().map(|c| format!("`{c}`")).collect::<Vec<_>>().join(", ")
+        ))
+    }
+}
+
+/// `unknown` for the key at `meta`
+pub(crate) fn unknown_meta(meta: &ParseNestedMeta, what: &str, candidates: &[&str]) -> Error {
+    match meta.path.get_ident() {
+        Some(key) => unknown(key.span(), what, &key.to_string(), candidates),
+        None      => meta.error(format!("unknown {what}"))
+    }
+}
+
+/// the most similar one of `candidates` to `name`, close enough to be a typo of it
+pub(crate) fn similar<'c>(name: &str, candidates: &[&'c str]) -> Option<&'c str> {
+    candidates.iter()
+        .map(|c| (distance(name, c), *c))
+        .filter(|(d, c)| *d > 0 && *d <= (c.len() / 3).max(1))
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, c)| c)
+}
+
+/// error for any directive left in `#[serdev(...)]`s of `attrs` after all the known ones are taken
+pub(crate) fn deny_unknown_serdev(attrs: &[Attribute], what: &str, candidates: &[&str]) -> Result<(), Error> {
+    match attr::directives(attrs, "serdev")?.first() {
+        None            => Ok(()),
+        Some(directive) => Err(unknown(
+            directive.path().span(), what, &directive.path().to_token_stream().to_string(), candidates
+        ))
+    }
+}
+
+/// optimal string alignment distance, counting a transposition like `valdiate` as one edit
+fn distance(a: &str, b: &str) -> usize {
+    let (a, b) = (a.chars().collect::<Vec<_>>(), b.chars().collect::<Vec<_>>());
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {row[0] = i}
+    for (j, cell) in d[0].iter_mut().enumerate() {*cell = j}
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i-1] != b[j-1]);
+            d[i][j] = (d[i-1][j] + 1).min(d[i][j-1] + 1).min(d[i-1][j-1] + cost);
+            if i > 1 && j > 1 && a[i-1] == b[j-2] && a[i-2] == b[j-1] {
+                d[i][j] = d[i][j].min(d[i-2][j-2] + 1)
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
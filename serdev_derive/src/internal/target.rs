@@ -1,6 +1,7 @@
-use proc_macro2::{Span, TokenStream};
+use super::{attr, keep::ProxyKeepAttrs, name, rules::{FieldRules, OnFailure, VariantRules}};
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
 use quote::{format_ident, quote, ToTokens};
-use syn::{parse::Parse, Attribute, Error, Fields, Generics, Ident, Item, ItemEnum, ItemStruct};
+use syn::{parse::Parse, parse_quote, punctuated::Punctuated, token, Attribute, Error, Field, Fields, Generics, Ident, Item, ItemEnum, ItemStruct, LitStr, Meta, Visibility};
 
 
 #[derive(Clone)]
@@ -14,7 +15,9 @@ impl Parse for Target {
         match input.parse::<Item>()? {
             Item::Enum(e)   => Ok(Self::Enum(e)),
             Item::Struct(s) => Ok(Self::Struct(s)),
-            _ => Err(Error::new(Span::call_site(), ""))
+            Item::Union(u)  => Err(Error::new(u.union_token.span, "serdev doesn't support unions, use a struct or an enum instead")),
+            Item::Type(t)   => Err(Error::new(t.type_token.span, "serdev can't be derived for a type alias, derive it on the aliased type instead")),
+            other           => Err(Error::new_spanned(other, "serdev can only be derived for structs and enums"))
         }
     }
 }
@@ -62,75 +65,228 @@ impl Target {
         }
     }
 
-    pub(crate) fn create_proxy(&self, name: Ident) -> Self {
+    pub(crate) fn vis_mut(&mut self) -> &mut Visibility {
+        match self {
+            Self::Enum(e)   => &mut e.vis,
+            Self::Struct(s) => &mut s.vis
+        }
+    }
+
+    /// proxy deserialized by serde in place of this type, and the marker types of its field names.
+    ///
+    /// The proxy has the same name as this type, so that serde's messages like "expected struct Point" don't
+    /// tell it from this type. It's declared in a scope of its own where this type is named `alias`, and
+    /// the references to this type in it are replaced with `alias`.
+    /// Its fields are wrapped by `Tracked` to attach their path to the errors in them where possible ( see `is_tracked` ).
+    pub(crate) fn create_proxy(&self, alias: &Ident, keep: &ProxyKeepAttrs, serdev: &TokenStream) -> Result<(Self, TokenStream), Error> {
         let mut proxy = self.clone();
 
-        *proxy.ident_mut() = name;
+        let ident = self.ident();
+        let self_ty = {
+            let (_, ty_generics, _) = self.generics().split_for_impl();
+            quote! {#alias #ty_generics}
+        };
+        let proxy_attrs = |attrs: &[Attribute]| -> Result<Vec<Attribute>, Error> {
+            attrs.iter()
+                .filter_map(|a| attr::for_proxy(a, keep))
+                .map(|a| rename_in_serde_paths(a, ident, alias))
+                .collect()
+        };
+        let proxy_field = |field: &mut Field| -> Result<(), Error> {
+            field.attrs = proxy_attrs(&field.attrs)?;
+            field.ty = syn::parse2(rename_type(field.ty.to_token_stream(), ident, alias, &self_ty))?;
+            Ok(())
+        };
 
-        *proxy.attrs_mut() = proxy.attrs().iter()
-            .filter(|a| a.path().get_ident().is_some_and(|i| i == "serde"))
-            .cloned().collect();
+        *proxy.attrs_mut() = proxy_attrs(proxy.attrs())?;
         match &mut proxy {
-            Self::Struct(s) => for field in &mut s.fields {
-                field.attrs = field.attrs.iter()
-                    .filter(|a| a.path().get_ident().is_some_and(|i| i == "serde"))
-                    .cloned().collect();
+            Self::Struct(s) => {
+                s.generics = rename_in_generics(&s.generics, ident, alias, &self_ty)?;
+                for field in &mut s.fields {
+                    proxy_field(field)?;
+                }
+            }
+            Self::Enum(e) => {
+                e.generics = rename_in_generics(&e.generics, ident, alias, &self_ty)?;
+                for variant in &mut e.variants {
+                    variant.attrs = proxy_attrs(&variant.attrs)?;
+                    for field in &mut variant.fields {
+                        proxy_field(field)?;
+                    }
+                }
+            }
+        }
+
+        let mut markers = TokenStream::new();
+        let mut n_markers = 0;
+        proxy.for_each_field(|field, name, variant_attrs, variant| {
+            if is_tracked(field, variant_attrs)? {
+                let marker = format_ident!("serdev_field_{n_markers}");
+                n_markers += 1;
+                let ty = &field.ty;
+                field.ty = parse_quote!(#serdev::__private__::Tracked<#ty, #marker>);
+                let variant = match variant {
+                    None          => quote! {::core::option::Option::None},
+                    Some(variant) => quote! {::core::option::Option::Some(#variant)}
+                };
+                markers.extend(quote! {
+                    #[allow(non_camel_case_types)]
+                    enum #marker {}
+                    impl #serdev::__private__::FieldName for #marker {
+                        const NAME:    &'static str = #name;
+                        const VARIANT: ::core::option::Option<&'static str> = #variant;
+                    }
+                });
+            }
+            Ok(())
+        })?;
+
+        Ok((proxy, markers))
+    }
+
+    /// whether to create the proxy even without validation, to attach the path to the errors
+    /// in its fields. Not for the containers serde deserializes in its own way, nor the ones having no tracked fields.
+    pub(crate) fn tracks_path(&mut self) -> Result<bool, Error> {
+        if has_serde_key(self.attrs(), &["from", "try_from", "remote", "transparent", "default"])? {
+            return Ok(false)
+        }
+        let mut tracks = false;
+        self.for_each_field(|field, _, variant_attrs, _| {
+            tracks |= is_tracked(field, variant_attrs)?;
+            Ok(())
+        })?;
+        Ok(tracks)
+    }
+
+    /// call `f` with each field, its name on the wire, the attributes of the variant it's in
+    /// and the variant's path segment ( see `name::variant_segment` )
+    fn for_each_field(&mut self,
+        mut f: impl FnMut(&mut Field, String, &[Attribute], Option<&str>) -> Result<(), Error>
+    ) -> Result<(), Error> {
+        match self {
+            Self::Struct(s) => {
+                let rename_all = name::rename_all(&s.attrs, "rename_all")?;
+                for (i, field) in s.fields.iter_mut().enumerate() {
+                    let name = name::field_name(field, i, rename_all)?;
+                    f(field, name, &[], None)?
+                }
             }
-            Self::Enum(e) => for variant in  &mut e.variants {
-                variant.attrs = variant.attrs.iter()
-                    .filter(|a| a.path().get_ident().is_some_and(|i| i == "serde"))
-                    .cloned().collect();
+            Self::Enum(e) => {
+                let rename_all_variants = name::rename_all(&e.attrs, "rename_all")?;
+                let rename_all_fields   = name::rename_all(&e.attrs, "rename_all_fields")?;
+                for variant in &mut e.variants {
+                    let segment = name::variant_segment(&e.attrs, variant, rename_all_variants)?;
+                    let rename_all = name::rename_all(&variant.attrs, "rename_all")?.or(rename_all_fields);
+                    for (i, field) in variant.fields.iter_mut().enumerate() {
+                        let name = name::field_name(field, i, rename_all)?;
+                        f(field, name, &variant.attrs, segment.as_deref())?
+                    }
+                }
             }
         }
+        Ok(())
+    }
+
+    /// take `VariantRules` of all variants (a struct is treated as a single variant)
+    pub(crate) fn take_rules(&mut self) -> Result<Vec<VariantRules>, Error> {
+        fn take_fields(fields: &mut Fields, rename_all: Option<name::RenameRule>, variant: Option<String>) -> Result<Vec<FieldRules>, Error> {
+            fields.iter_mut().enumerate()
+                .map(|(i, field)| {
+                    let name = name::field_name(field, i, rename_all)?;
+                    FieldRules::take(field, name, variant.clone())
+                })
+                .collect()
+        }
 
-        proxy
+        match self {
+            Self::Struct(s) => {
+                let rename_all = name::rename_all(&s.attrs, "rename_all")?;
+                Ok(vec![VariantRules::of_struct(take_fields(&mut s.fields, rename_all, None)?)])
+            }
+            Self::Enum(e) => {
+                let rename_all        = name::rename_all(&e.attrs, "rename_all")?;
+                let rename_all_fields = name::rename_all(&e.attrs, "rename_all_fields")?;
+                e.variants.iter_mut()
+                    .map(|v| {
+                        let segment = name::variant_segment(&e.attrs, v, rename_all)?;
+                        let fields  = take_fields(&mut v.fields, name::rename_all(&v.attrs, "rename_all")?.or(rename_all_fields), segment.clone())?;
+                        VariantRules::take(v, segment, fields)
+                    })
+                    .collect()
+            }
+        }
     }
 
+    /// expression converting `variable_ident` of this type into `target_ident`,
+    /// sanitizing the fields by `rules` (from `take_rules`) on the way
     pub(crate) fn transmute_expr(&self,
         variable_ident: &'static str,
-        target_ident:   &Ident
-    ) -> TokenStream {
+        target_ident:   &Ident,
+        rules:          &[VariantRules],
+        serdev:         &TokenStream,
+    ) -> Result<TokenStream, Error> {
         let var = Ident::new(variable_ident, Span::call_site());
 
-        fn constructor(fields: &Fields) -> TokenStream {
-            match fields {
+        /// pattern destructuring the proxy's `fields` into `bindings`, unwrapping `Tracked` ones
+        /// and binding the ones to be sanitized as mutable ( in shorthand where possible, as the
+        /// named bindings are the same as the field names )
+        fn pattern(fields: &Fields, bindings: &[Ident], rules: &VariantRules, variant_attrs: &[Attribute], serdev: &TokenStream) -> Result<TokenStream, Error> {
+            let bindings = fields.iter().zip(bindings).enumerate()
+                .map(|(i, (field, binding))| {
+                    let binding = if rules.sanitizes_field(i) {quote! {mut #binding}} else {quote! {#binding}};
+                    Ok(match (is_tracked(field, variant_attrs)?, &field.ident) {
+                        (true, Some(ident)) => quote! {#ident: #serdev::__private__::Tracked(#binding, _)},
+                        (true, None)        => quote! {#serdev::__private__::Tracked(#binding, _)},
+                        (false, _)          => binding,
+                    })
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+            Ok(match fields {
                 Fields::Unit => {
                     quote! {}
                 }
-                Fields::Unnamed(u) => {
-                    let idents = (0..u.unnamed.len()).map(|i| format_ident!("field_{i}"));
+                Fields::Unnamed(_) => {
                     quote! {
-                        ( #(#idents),* )
+                        ( #(#bindings),* )
                     }
                 }
-                Fields::Named(n) => {
-                    let idents = n.named.iter().map(|f| f.ident.as_ref().unwrap());
+                Fields::Named(_) => {
                     quote! {
-                        { #(#idents),* }
+                        { #(#bindings),* }
                     }
                 }
-            }
+            })
         }
 
-        match self {
+        Ok(match self {
             Self::Struct(s) => {
                 let ident = &s.ident;
-                let constructor = constructor(&s.fields);
+                let bindings    = bindings(&s.fields);
+                let pattern     = pattern(&s.fields, &bindings, &rules[0], &[], serdev)?;
+                let constructor = constructor(&s.fields, &bindings);
+                let sanitize    = rules[0].sanitize_stmts(&bindings, serdev);
                 quote! {{
-                    let #ident #constructor = #var;
+                    let #ident #pattern = #var;
+                    #sanitize
                     #target_ident #constructor
                 }}
             }
             Self::Enum(e) => {
                 let ident = &e.ident;
 
-                let arms = e.variants.iter().map(|v| {
-                    let variant = &v.ident;
-                    let fields  = constructor(&v.fields);
-                    quote! {
-                        #ident::#variant #fields => #target_ident::#variant #fields
-                    }
-                });
+                let arms = e.variants.iter().zip(rules).map(|(v, rules)| {
+                    let variant  = &v.ident;
+                    let bindings = bindings(&v.fields);
+                    let pattern  = pattern(&v.fields, &bindings, rules, &v.attrs, serdev)?;
+                    let fields   = constructor(&v.fields, &bindings);
+                    let sanitize = rules.sanitize_stmts(&bindings, serdev);
+                    Ok(quote! {
+                        #ident::#variant #pattern => {
+                            #sanitize
+                            #target_ident::#variant #fields
+                        }
+                    })
+                }).collect::<Result<Vec<_>, Error>>()?;
 
                 quote! {
                     match #var {
@@ -138,6 +294,194 @@ impl Target {
                     }
                 }
             }
+        })
+    }
+
+    /// statements running the checks of `rules` (from `take_rules`) on `self`
+    /// in a method of this type taking `&self`. `custom_error` tells this type's error type
+    /// is given by `error = "..."` ( see `VariantRules::checks` )
+    pub(crate) fn check_stmts(&self,
+        rules:        &[VariantRules],
+        serdev:       &TokenStream,
+        on_failure:   OnFailure,
+        custom_error: bool,
+    ) -> Result<TokenStream, Error> {
+        if rules.iter().all(VariantRules::is_empty) {
+            return Ok(TokenStream::new())
+        }
+        self.for_each_variant(quote! {self}, rules, |rules, bindings| {
+            rules.checks(bindings, serdev, on_failure, custom_error)
+        })
+    }
+
+    /// statements running the field normalizers of `rules` (from `take_rules`)
+    /// on `this`, a mutable reference to a value of this type
+    pub(crate) fn normalize_stmts(&self,
+        this:  TokenStream,
+        rules: &[VariantRules],
+    ) -> Result<TokenStream, Error> {
+        if !rules.iter().any(VariantRules::normalizes) {
+            return Ok(TokenStream::new())
+        }
+        self.for_each_variant(this, rules, |rules, bindings| {
+            Ok(rules.normalize_stmts(bindings))
+        })
+    }
+
+    /// statements running `stmts` for the variant of `this`, a reference to a value
+    /// of this type, with its fields bound to the references to them
+    fn for_each_variant(&self,
+        this:  TokenStream,
+        rules: &[VariantRules],
+        stmts: impl Fn(&VariantRules, &[Ident]) -> Result<TokenStream, Error>,
+    ) -> Result<TokenStream, Error> {
+        Ok(match self {
+            Self::Struct(s) => {
+                let bindings    = bindings(&s.fields);
+                let constructor = constructor(&s.fields, &bindings);
+                let stmts       = stmts(&rules[0], &bindings)?;
+                quote! {
+                    #[allow(unused_variables)]
+                    let Self #constructor = #this;
+                    #stmts
+                }
+            }
+            Self::Enum(e) => {
+                let arms = e.variants.iter().zip(rules).map(|(v, rules)| {
+                    let variant  = &v.ident;
+                    let bindings = bindings(&v.fields);
+                    let fields   = constructor(&v.fields, &bindings);
+                    let stmts    = stmts(rules, &bindings)?;
+                    Ok(quote! {
+                        #[allow(unused_variables)]
+                        Self::#variant #fields => {
+                            #stmts
+                        }
+                    })
+                }).collect::<Result<Vec<_>, Error>>()?;
+
+                quote! {
+                    match #this {
+                        #(#arms),*
+                    }
+                }
+            }
+        })
+    }
+}
+
+fn bindings(fields: &Fields) -> Vec<Ident> {
+    fields.iter().enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => ident.clone(),
+            None        => format_ident!("field_{i}")
+        })
+        .collect()
+}
+
+fn constructor(fields: &Fields, bindings: &[Ident]) -> TokenStream {
+    match fields {
+        Fields::Unit => {
+            quote! {}
+        }
+        Fields::Unnamed(_) => {
+            quote! {
+                ( #(#bindings),* )
+            }
+        }
+        Fields::Named(_) => {
+            quote! {
+                { #(#bindings),* }
+            }
         }
     }
 }
+
+/// whether a field of the proxy is wrapped by `Tracked`: not for the ones serde deserializes
+/// in its own way, and ones containing references, whose borrowing serde detects by their types
+/// like `&'a str` or `Option<&'a str>`
+fn is_tracked(field: &Field, variant_attrs: &[Attribute]) -> Result<bool, Error> {
+    fn has_reference(ty: TokenStream) -> bool {
+        ty.into_iter().any(|tt| match tt {
+            TokenTree::Punct(p) => p.as_char() == '&',
+            TokenTree::Group(g) => has_reference(g.stream()),
+            _ => false
+        })
+    }
+
+    Ok(!has_reference(field.ty.to_token_stream())
+        && !has_serde_key(&field.attrs, &["with", "deserialize_with", "flatten", "skip", "skip_deserializing", "borrow"])?
+        && !has_serde_key(variant_attrs, &["with", "deserialize_with", "skip", "skip_deserializing"])?
+        && !has_serde_default_path(&field.attrs)?
+    )
+}
+
+fn has_serde_key(attrs: &[Attribute], keys: &[&str]) -> Result<bool, Error> {
+    Ok(attr::directives(attrs, "serde")?.iter()
+        .any(|d| keys.iter().any(|key| d.path().is_ident(key))))
+}
+
+/// `#[serde(default = "path")]`, whose function returns the field's type
+fn has_serde_default_path(attrs: &[Attribute]) -> Result<bool, Error> {
+    Ok(attr::directives(attrs, "serde")?.iter()
+        .any(|d| matches!(d, Meta::NameValue(nv) if nv.path.is_ident("default"))))
+}
+
+/// `tokens` with the paths starting with `ident`, the name of the target, renamed to `alias`,
+/// and `Self` replaced with `self_ty`
+fn rename_type(tokens: TokenStream, ident: &Ident, alias: &Ident, self_ty: &TokenStream) -> TokenStream {
+    let mut renamed = TokenStream::new();
+    let (mut after_colon, mut after_colon2) = (false, false);
+    for tree in tokens {
+        let is_colon = matches!(&tree, TokenTree::Punct(p) if p.as_char() == ':');
+        match tree {
+            TokenTree::Ident(i) if i == "Self" => {
+                renamed.extend(self_ty.clone())
+            }
+            TokenTree::Ident(i) if i == *ident && !after_colon2 => {
+                renamed.extend([TokenTree::Ident(Ident::new(&alias.to_string(), i.span()))])
+            }
+            TokenTree::Group(g) => {
+                let mut group = Group::new(g.delimiter(), rename_type(g.stream(), ident, alias, self_ty));
+                group.set_span(g.span());
+                renamed.extend([TokenTree::Group(group)])
+            }
+            other => renamed.extend([other])
+        }
+        (after_colon, after_colon2) = (is_colon, is_colon && after_colon);
+    }
+    renamed
+}
+
+fn rename_in_generics(generics: &Generics, ident: &Ident, alias: &Ident, self_ty: &TokenStream) -> Result<Generics, Error> {
+    let mut renamed: Generics = syn::parse2(rename_type(generics.to_token_stream(), ident, alias, self_ty))?;
+    renamed.where_clause = generics.where_clause.as_ref()
+        .map(|w| syn::parse2(rename_type(w.to_token_stream(), ident, alias, self_ty)))
+        .transpose()?;
+    Ok(renamed)
+}
+
+/// `attr` with the target renamed to `alias` in the paths of `#[serde(...)]` directives
+/// taking a function, like `default = "Point::origin"`
+fn rename_in_serde_paths(mut attr: Attribute, ident: &Ident, alias: &Ident) -> Result<Attribute, Error> {
+    if !attr.path().is_ident("serde") {
+        return Ok(attr)
+    }
+    let directives = attr::directives(std::slice::from_ref(&attr), "serde")?.into_iter()
+        .map(|directive| match directive {
+            Meta::NameValue(mut nv) if nv.path.is_ident("default") || nv.path.is_ident("deserialize_with") => {
+                if let Some(path) = attr::litstr(&nv.value) {
+                    let renamed = LitStr::new(
+                        &rename_type(path.parse()?, ident, alias, &alias.to_token_stream()).to_string(),
+                        path.span()
+                    );
+                    nv.value = parse_quote!(#renamed);
+                }
+                Ok(Meta::NameValue(nv))
+            }
+            other => Ok(other)
+        })
+        .collect::<Result<Punctuated<Meta, token::Comma>, Error>>()?;
+    attr.meta = parse_quote!(serde(#directives));
+    Ok(attr)
+}
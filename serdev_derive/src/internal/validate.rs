@@ -1,97 +1,181 @@
-use proc_macro2::{Span, TokenStream};
-use syn::{parse::Parse, punctuated::Punctuated, spanned::Spanned, token, Attribute, Error, Ident, LitStr, MacroDelimiter, Meta, MetaList, Path};
+use super::{attr, suggest};
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{spanned::Spanned, token, Attribute, Error, Expr, ExprPath, Ident, LitStr, Meta};
 
 
 mod keyword {
     syn::custom_keyword! { by }
     syn::custom_keyword! { error }
+    syn::custom_keyword! { expr }
+    syn::custom_keyword! { message }
 }
 
+/// serdev's own keys in `#[serde(...)]`
+const SERDEV_KEYS: &[&str] = &["validate", "normalize", "finalize"];
+
 pub(crate) enum Validate {
-    Eq(LitStr),
-    Paren { by: LitStr, error: Option<LitStr> },
+    Eq(Expr),
+    Paren { by: Expr, error: Option<LitStr> },
+    /// `validate(expr = "...", message = "...", expr = "...", ...)`, each `message` for the `expr` just before it
+    Exprs(Vec<(Expr, String)>),
 }
 
-impl Parse for Validate {
-    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let _validate = input.parse::<Ident>()?;
-        if _validate != "validate" {
-            return Err(Error::new(Span::call_site(), "expected `validate`"))
-        }
+/// validator function given as a path or a closure, quoted or not
+fn validator(expr: Expr) -> Result<Expr, Error> {
+    let validator = match attr::litstr(&expr) {
+        Some(quoted) => quoted.parse()?,
+        None         => expr
+    };
+    match validator {
+        Expr::Path(_) | Expr::Closure(_) => Ok(validator),
+        _ => Err(Error::new(validator.span(), "expected a path to function or a closure"))
+    }
+}
 
-        if input.peek(token::Eq) {
-            input.parse::<token::Eq>()?;
-            let by: LitStr = input.parse()?;
-            Ok(Validate::Eq(by))
-
-        } else if input.peek(token::Paren) {
-            let buf; syn::parenthesized!(buf in input);
-            let mut by    = None;
-            let mut error = None;
-            while !buf.is_empty() {
-                if buf.peek(token::Comma) {
-                    buf.parse::<token::Comma>()?;
-                } else if buf.peek(keyword::by) {
-                    buf.parse::<keyword::by>()?;
-                    buf.parse::<token::Eq>()?;
-                    by = Some(buf.parse()?)
-                } else if buf.peek(keyword::error) {
-                    buf.parse::<keyword::error>()?;
-                    buf.parse::<token::Eq>()?;
-                    error = Some(buf.parse()?)
-                } else {
-                    let rest = buf.parse::<TokenStream>()?;
-                    if !rest.is_empty() {
-                        return Err(Error::new(rest.span(), "expected `by = \"...\"` or `error = \"...\"`"))
+impl Validate {
+    /// `validate = validator`, `validate = [validator, ...]` or `validate(...)`
+    fn parse(directive: Meta) -> Result<Vec<Self>, Error> {
+        match directive {
+            Meta::NameValue(nv) => match nv.value {
+                Expr::Array(array) => array.elems.into_iter()
+                    .map(|v| validator(v).map(Validate::Eq))
+                    .collect(),
+                by => Ok(vec![Validate::Eq(validator(by)?)])
+            }
+            Meta::List(list) => list.parse_args_with(|buf: syn::parse::ParseStream| {
+                let mut by    = None;
+                let mut error = None;
+                let mut exprs = Vec::<(Expr, String)>::new();
+                while !buf.is_empty() {
+                    if buf.peek(token::Comma) {
+                        buf.parse::<token::Comma>()?;
+                    } else if buf.peek(keyword::by) {
+                        buf.parse::<keyword::by>()?;
+                        buf.parse::<token::Eq>()?;
+                        by = Some(validator(buf.parse()?)?)
+                    } else if buf.peek(keyword::error) {
+                        buf.parse::<keyword::error>()?;
+                        buf.parse::<token::Eq>()?;
+                        error = Some(buf.parse()?)
+                    } else if buf.peek(keyword::expr) {
+                        buf.parse::<keyword::expr>()?;
+                        buf.parse::<token::Eq>()?;
+                        let expr = buf.parse::<LitStr>()?;
+                        let message = format!("must satisfy `{}`", expr.value());
+                        exprs.push((expr.parse()?, message))
+                    } else if buf.peek(keyword::message) {
+                        let message = buf.parse::<keyword::message>()?;
+                        buf.parse::<token::Eq>()?;
+                        match exprs.last_mut() {
+                            Some((_, m)) => *m = buf.parse::<LitStr>()?.value(),
+                            None => return Err(Error::new(message.span, "expected `expr = \"...\"` before `message`"))
+                        }
                     } else {
-                        return Err(Error::new(rest.span(), format!("rest: `{}`", rest.to_string())))
+                        return Err(match buf.parse::<Ident>() {
+                            Ok(key) => suggest::unknown(key.span(), "validate key", &key.to_string(), &["by", "error", "expr", "message"]),
+                            Err(_)  => buf.error("expected `by = \"...\"`, `error = \"...\"`, `expr = \"...\"` or `message = \"...\"`")
+                        })
                     }
                 }
-            }
-            let by = by.ok_or(Error::new(Span::call_site(), "expected `by = \"...\"`"))?;
-            Ok(Validate::Paren { by, error })
-
-        } else {
-            Err(Error::new(Span::call_site(), "expected `validate = \"...\"` or `validate(by = \"...\", error = \"...\")`"))
+                match (by, exprs.is_empty()) {
+                    (Some(by), true) => Ok(vec![Validate::Paren { by, error }]),
+                    (None, false) => match error {
+                        None        => Ok(vec![Validate::Exprs(exprs)]),
+                        Some(error) => Err(Error::new(error.span(), "`error` is not available with `expr`"))
+                    },
+                    (Some(by), false) => Err(Error::new(by.span(), "`by` and `expr` can't be used together")),
+                    (None, true) => Err(Error::new(list.delimiter.span().join(), "expected `by = \"...\"` or `expr = \"...\"`")),
+                }
+            }),
+            Meta::Path(path) => Err(Error::new(path.span(), "expected `validate = \"...\"`, `validate = [\"...\", ...]`, `validate(by = \"...\", error = \"...\")` or `validate(expr = \"...\", message = \"...\")`"))
         }
     }
-}
 
-impl Validate {
-    pub(crate) fn take(attrs: &mut Vec<Attribute>) -> Result<Option<Self>, Error> {
-        for attr in attrs {
-            if attr.path().get_ident().is_some_and(|i| i == "serde") {
-                let directives = attr.parse_args_with(
-                    Punctuated::<TokenStream, token::Comma>::parse_terminated
-                )?;
-                for (i, directive) in directives.iter().enumerate() {
-                    if directive.to_string().starts_with("validate") {
-                        attr.meta = Meta::List(MetaList {
-                            path:      syn::parse_str("serde")?,
-                            delimiter: MacroDelimiter::Paren(token::Paren::default()),
-                            tokens:    syn::parse_str(&{
-                                let mut others = String::new();
-                                for (j, directive) in directives.iter().enumerate() {
-                                    if j != i {
-                                        others.push_str(&directive.to_string());
-                                        others.push(',')
-                                    }
-                                }; others.pop();
-                                others
-                            })?
-                        });
-                        return syn::parse2(directive.clone()).map(Some)
-                    }
+    /// take all the `validate` directives out of `#[serde(...)]`s in `attrs`, in the declared order
+    pub(crate) fn take(attrs: &mut [Attribute]) -> Result<Vec<Self>, Error> {
+        let mut validates = Vec::new();
+        for directive in attr::take(attrs, "serde", "validate")? {
+            validates.extend(Self::parse(directive)?)
+        }
+
+        /* serde doesn't know serdev's keys to suggest them for typos */
+        for directive in attr::directives(attrs, "serde")? {
+            if let Some(key) = directive.path().get_ident() {
+                if suggest::similar(&key.to_string(), SERDEV_KEYS).is_some() {
+                    return Err(suggest::unknown(key.span(), "serde attribute", &key.to_string(), SERDEV_KEYS))
                 }
             }
-        }; Ok(None)
+        }
+
+        Ok(validates)
     }
 
-    pub(crate) fn function(&self) -> Result<Path, Error> {
-        syn::parse_str(&match self {
-            Self::Eq(by) => by,
-            Self::Paren { by, error:_ } => by
-        }.value())
+    /// expressions of `Result<(), _>` validating `args`, references to values of `types`: one calling the function,
+    /// or one for each of the inline expressions having `args` already in scope, so that all of them are
+    /// checked for `#[serdev(collect_errors)]`.
+    /// `collected` tells the `error` type is the collection of errors for `#[serdev(collect_errors)]`.
+    pub(crate) fn call(&self, args: &[TokenStream], types: &[TokenStream], collected: bool) -> Result<Vec<TokenStream>, Error> {
+        match self {
+            Self::Eq(by) | Self::Paren { by, error:_ } => match by {
+                Expr::Path(function) => self.checked_call(function, args, types, collected).map(|call| vec![call]),
+                closure => Ok(vec![quote! {(#closure)(#(#args),*)}])
+            }
+            Self::Exprs(exprs) => Ok(exprs.iter().map(|(expr, message)| quote_spanned! {expr.span()=>
+                (if #expr {
+                    ::core::result::Result::<(), &'static str>::Ok(())
+                } else {
+                    ::core::result::Result::Err(#message)
+                })
+            }).collect())
+        }
+    }
+
+    /// call of `function` with `args`, references to values of `types`, whose result is passed through
+    /// a trait implemented only for the expected `Result`, to report the signature on `function` for
+    /// wrong ones instead of type errors in the generated code. The call itself is spanned on `function`
+    /// too, so that mismatched arguments are reported there while keeping deref coercion like `&String` to `&str`.
+    fn checked_call(&self, function: &ExprPath, args: &[TokenStream], types: &[TokenStream], collected: bool) -> Result<TokenStream, Error> {
+        let error = self.error()?;
+
+        let params = types.iter().map(|t| format!("&{}", pretty(t))).collect::<Vec<_>>().join(", ");
+        let signature = match &error {
+            None                  => format!("`fn({params}) -> Result<(), impl Display>`"),
+            Some(ty) if collected => format!("`fn({params}) -> Result<(), E>` where `{}: Extend<E>`", pretty(ty)),
+            Some(ty)              => format!("`fn({params}) -> Result<(), {}>`", pretty(ty)),
+        };
+        let message = format!("validator `{}` must be {signature}", pretty(&function.to_token_stream()));
+
+        let (collection, mode, mode_bound, mode_arg) = match &error {
+            None                  => (quote! {}, quote! {()}, quote! {E: ::core::fmt::Display,}, quote! {()}),
+            Some(ty) if collected => (quote! {C,}, quote! {C}, quote! {C: ::core::iter::Extend<E>,}, ty.clone()),
+            Some(ty)              => (quote! {}, quote! {E}, quote! {}, ty.clone()),
+        };
+
+        let args = args.iter().map(|arg| respan(arg.clone(), function.span()));
+
+        Ok(quote_spanned! {function.span()=> {
+            #[allow(non_camel_case_types)]
+            #[diagnostic::on_unimplemented(message = #message, label = "validator of a wrong signature")]
+            trait serdev_validation<Mode> {
+                type Error;
+                fn serdev_result(self) -> ::core::result::Result<(), Self::Error>;
+            }
+            #[diagnostic::do_not_recommend]
+            impl<E, #collection> serdev_validation<#mode> for ::core::result::Result<(), E>
+            where
+                #mode_bound
+            {
+                type Error = E;
+                #[inline]
+                fn serdev_result(self) -> ::core::result::Result<(), E> {self}
+            }
+            #[inline]
+            fn serdev_checked<Mode, R: serdev_validation<Mode>>(result: R) -> ::core::result::Result<(), R::Error> {
+                result.serdev_result()
+            }
+            serdev_checked::<#mode_arg, _>(#function(#(#args),*))
+        }})
     }
 
     pub(crate) fn error(&self) -> Result<Option<TokenStream>, Error> {
@@ -101,3 +185,24 @@ impl Validate {
         }
     }
 }
+
+/// tokens printed like Rust code, without the spaces `to_string` puts around punctuations
+fn pretty(tokens: &TokenStream) -> String {
+    tokens.to_string()
+        .replace(" :: ", "::")
+        .replace(" < ", "<").replace("< ", "<").replace(" <", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+        .replace("& ", "&")
+}
+
+/// `tokens` all spanned on `span`
+pub(crate) fn respan(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens.into_iter().map(|mut tree| {
+        if let TokenTree::Group(group) = &tree {
+            tree = TokenTree::Group(Group::new(group.delimiter(), respan(group.stream(), span)))
+        }
+        tree.set_span(span);
+        tree
+    }).collect()
+}
@@ -0,0 +1,40 @@
+use super::{attr, suggest};
+use syn::{Attribute, Error};
+
+
+/// `#[serdev(validate_on = "deserialize" | "serialize" | "both")]`,
+/// `"deserialize"` by default
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum ValidateOn {
+    Deserialize,
+    Serialize,
+    Both,
+}
+
+impl ValidateOn {
+    /// `validates` is whether the type has anything to validate, without which validating on serialization is an error
+    pub(crate) fn take(attrs: &mut [Attribute], validates: bool) -> Result<Self, Error> {
+        let Some(directive) = attr::take_one(attrs, "serdev", "validate_on")? else {
+            return Ok(Self::Deserialize)
+        };
+        let on = attr::litstr_value(&directive)?;
+        let this = match &*on.value() {
+            "deserialize" => Self::Deserialize,
+            "serialize"   => Self::Serialize,
+            "both"        => Self::Both,
+            other => return Err(suggest::unknown(on.span(), "validate_on", other, &["deserialize", "serialize", "both"]))
+        };
+        if this.on_serialize() && !validates {
+            return Err(Error::new(on.span(), "nothing to validate: no rules or `#[serde(validate = ...)]` on this type"))
+        }
+        Ok(this)
+    }
+
+    pub(crate) fn on_deserialize(self) -> bool {
+        matches!(self, Self::Deserialize | Self::Both)
+    }
+
+    pub(crate) fn on_serialize(self) -> bool {
+        matches!(self, Self::Serialize | Self::Both)
+    }
+}
@@ -16,6 +16,13 @@ pub fn Deserialize(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         .into()
 }
 
+#[proc_macro_derive(Validate, attributes(serde, serdev))]
+pub fn Validate(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    internal::Validate(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
 #[doc(hidden)]
 #[proc_macro_attribute]
 pub fn consume(_: proc_macro::TokenStream, _: proc_macro::TokenStream) -> proc_macro::TokenStream {